@@ -0,0 +1,181 @@
+//! Implementation of the `#[imgui_ext::actions]` attribute macro.
+//!
+//! Every method of the impl block annotated with `#[imgui(button)]` is turned
+//! into an `Action`, so the buttons (and the widgets of the method arguments)
+//! are emitted by the same code as the `actions(...)` attribute of the derive.
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{FnArg, Ident, ImplItem, ItemImpl, Lit, Meta, MetaNameValue, NestedMeta, Pat, Type};
+
+use crate::error::Error;
+use crate::parser::{self, Action};
+
+pub fn impl_actions(args: &[NestedMeta], item: &mut ItemImpl) -> Result<TokenStream, Error> {
+    let args_field = args_field(args)?;
+    let actions = collect_actions(item)?;
+
+    let name = match &*item.self_ty {
+        Type::Path(path) => match path.path.segments.last() {
+            Some(segment) => segment.ident.clone(),
+            None => return Err(Error::invalid_format(item.self_ty.span())),
+        },
+        ty => return Err(Error::invalid_format(ty.span())),
+    };
+    let ty = &item.self_ty;
+    let (impl_generics, _, where_clause) = item.generics.split_for_impl();
+    let args_types = actions.iter().filter_map(Action::args_type);
+
+    let mut fields = TokenStream::new();
+    let mut methods = TokenStream::new();
+    let body = parser::emmit_actions(
+        &actions,
+        &mut fields,
+        &mut methods,
+        &mut HashSet::new(),
+        args_field.as_ref(),
+        parser::Mode::Edit,
+    )?;
+    let readonly_body = parser::emmit_actions(
+        &actions,
+        &mut TokenStream::new(),
+        &mut TokenStream::new(),
+        &mut HashSet::new(),
+        None,
+        parser::Mode::ReadOnly,
+    )?;
+
+    // It should never generate a collision with the events of the derive, nor
+    // with other impl blocks of the same type (`impl Foo<i32>`, `impl Foo<f32>`)
+    let mut hasher = DefaultHasher::new();
+    quote!(#impl_generics #ty #where_clause)
+        .to_string()
+        .hash(&mut hasher);
+    let event_type = Ident::new(
+        &format!("__{}_Actions_{:x}", name.unraw(), hasher.finish()),
+        name.span(),
+    );
+
+    Ok(quote! {
+        #[allow(non_camel_case_types)]
+        #[derive(Default)]
+        pub struct #event_type {
+            #fields
+        }
+        impl #event_type {
+            #methods
+        }
+        impl #impl_generics imgui_ext::Actions for #ty #where_clause {
+            type Events = #event_type;
+            type Args = ( #( #args_types , )* );
+            fn draw_actions(ui: &imgui::Ui, ext: &mut Self) -> Self::Events {
+                let mut events = Self::Events::default();
+                #body
+                events
+            }
+            #[allow(unused_mut, unused_variables)]
+            fn draw_actions_readonly(ui: &imgui::Ui, ext: &Self) -> Self::Events {
+                let mut events = Self::Events::default();
+                #readonly_body
+                events
+            }
+        }
+    })
+}
+
+// Field of the struct keeping the values of the arguments of the methods
+// between frames (`#[imgui_ext::actions(args = "field")]`).
+fn args_field(args: &[NestedMeta]) -> Result<Option<Ident>, Error> {
+    let mut field = None;
+    for arg in args {
+        match arg {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. }))
+                if path.is_ident("args") =>
+            {
+                match lit {
+                    Lit::Str(lit) if field.is_none() => field = Some(parser::lit_ident(lit)?),
+                    Lit::Str(_) => return Err(Error::already_defined(path.span())),
+                    lit => return Err(Error::expected(lit.span(), "a string literal")),
+                }
+            }
+            NestedMeta::Meta(meta) => {
+                let name = meta
+                    .path()
+                    .get_ident()
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+                return Err(Error::unknown_param(meta.span(), &name, &["args"]));
+            }
+            NestedMeta::Lit(lit) => return Err(Error::invalid_format(lit.span())),
+        }
+    }
+    Ok(field)
+}
+
+// Removes the `#[imgui(...)]` annotations from the methods of the impl block
+// (they are not valid attributes for the compiler) and collects their actions.
+fn collect_actions(item: &mut ItemImpl) -> Result<Vec<Action>, Error> {
+    let mut actions = Vec::new();
+    let mut error = None;
+
+    for impl_item in item.items.iter_mut() {
+        let method = match impl_item {
+            ImplItem::Method(method) => method,
+            _ => continue,
+        };
+
+        let (attrs, rest) = method
+            .attrs
+            .drain(..)
+            .partition::<Vec<_>, _>(|attr| attr.path.is_ident("imgui"));
+        method.attrs = rest;
+
+        let mut attrs = attrs.into_iter();
+        let attr = match (attrs.next(), attrs.next()) {
+            (None, _) => continue,
+            (Some(_), Some(extra)) => {
+                error = error.or_else(|| Some(Error::multiple(extra.span())));
+                continue;
+            }
+            (Some(attr), None) => attr,
+        };
+
+        let action = method_args(&method.sig)
+            .and_then(|args| Action::from_method(&attr, method.sig.ident.clone(), args));
+        match action {
+            Ok(action) => actions.push(action),
+            Err(err) => error = error.or(Some(err)),
+        }
+    }
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(actions),
+    }
+}
+
+// Arguments of the method, which must take `&mut self` as the receiver.
+fn method_args(sig: &syn::Signature) -> Result<Vec<(Ident, Type)>, Error> {
+    let mut inputs = sig.inputs.iter();
+    match inputs.next() {
+        Some(FnArg::Receiver(receiver))
+            if receiver.reference.is_some() && receiver.mutability.is_some() => {}
+        Some(arg) => return Err(Error::receiver(arg.span())),
+        None => return Err(Error::receiver(sig.ident.span())),
+    }
+
+    inputs
+        .map(|arg| match arg {
+            FnArg::Typed(pat) => match &*pat.pat {
+                Pat::Ident(ident) => Ok((ident.ident.clone(), (*pat.ty).clone())),
+                pat => Err(Error::invalid_format(pat.span())),
+            },
+            FnArg::Receiver(receiver) => Err(Error::receiver(receiver.span())),
+        })
+        .collect()
+}
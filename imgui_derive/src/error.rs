@@ -2,17 +2,22 @@ use proc_macro2::TokenStream;
 use std::fmt;
 use syn::export::Span;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ErrorKind {
     InvalidFormat,
     Multiple,
     NonStruct,
     UnexpectedMode,
-    UnexpectedParam,
     Bullet,
     AlreadyDefined,
-    ParseError,
     MissingParam(&'static str),
+    MissingDoc,
+    Receiver,
+    UnknownAnnotation(String),
+    UnknownParam(String),
+    InvalidPath(String),
+    Expected(&'static str),
+    Syn(String),
 }
 
 impl fmt::Display for ErrorKind {
@@ -25,14 +30,19 @@ impl fmt::Display for ErrorKind {
                 "ImGuiExt macro is only supported for structs with named fields."
             ),
             ErrorKind::UnexpectedMode => write!(fmt, "Unexpected annotation."),
-            ErrorKind::UnexpectedParam => write!(fmt, "Unexpected parameter."),
             ErrorKind::Bullet => write!(
                 fmt,
                 "Multiple nested annotations inside of a bullet list element."
             ),
             ErrorKind::AlreadyDefined => write!(fmt, "Field is defined already."),
-            ErrorKind::ParseError => write!(fmt, "String parsing error."),
             ErrorKind::MissingParam(p) => write!(fmt, "Parameter `{}` missing.", p),
+            ErrorKind::MissingDoc => write!(fmt, "Field has no doc comment to take the text from."),
+            ErrorKind::Receiver => write!(fmt, "Action methods must take `&mut self`."),
+            ErrorKind::UnknownAnnotation(name) => write!(fmt, "Unknown annotation `{}`.", name),
+            ErrorKind::UnknownParam(name) => write!(fmt, "Unexpected parameter `{}`.", name),
+            ErrorKind::InvalidPath(path) => write!(fmt, "Invalid path `{}`.", path),
+            ErrorKind::Expected(what) => write!(fmt, "Expected {}.", what),
+            ErrorKind::Syn(err) => write!(fmt, "{}", err),
         }
     }
 }
@@ -41,87 +51,201 @@ impl fmt::Display for ErrorKind {
 pub struct Error {
     kind: ErrorKind,
     span: Span,
+    // extra lines of the message (suggestions, accepted values, ...)
+    help: Vec<String>,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.kind)?;
+        for help in self.help.iter() {
+            write!(fmt, "\n{}", help)?;
+        }
+        Ok(())
+    }
 }
 
 impl From<Error> for syn::Error {
     fn from(err: Error) -> Self {
-        syn::Error::new(err.span, err.kind)
+        syn::Error::new(err.span, err)
+    }
+}
+
+impl From<syn::Error> for Error {
+    fn from(err: syn::Error) -> Self {
+        Self::new(ErrorKind::Syn(err.to_string()), err.span())
     }
 }
 
 impl Error {
     pub fn new(kind: ErrorKind, span: Span) -> Self {
-        Self { kind, span }
+        Self {
+            kind,
+            span,
+            help: Vec::new(),
+        }
     }
 
     pub fn to_compile_error(&self) -> TokenStream {
-        syn::Error::new(self.span.clone(), self.kind).to_compile_error()
+        syn::Error::new(self.span.clone(), self).to_compile_error()
     }
 
-    pub fn missing_param(span: Span, name: &'static str) -> Self {
-        Self {
-            kind: ErrorKind::MissingParam(name),
-            span,
+    /// Adds a line to the message.
+    pub fn help<S: Into<String>>(mut self, help: S) -> Self {
+        self.help.push(help.into());
+        self
+    }
+
+    /// Suggests the closest of the `accepted` names (if `name` looks like a
+    /// typo), and lists all of them.
+    fn accepted(self, name: &str, accepted: &[&str], what: &str) -> Self {
+        let error = match suggest(name, accepted) {
+            Some(suggestion) => self.help(format!("Did you mean `{}`?", suggestion)),
+            None => self,
+        };
+        if accepted.is_empty() {
+            error.help("The annotation takes no parameters.")
+        } else {
+            let accepted: Vec<_> = accepted.iter().map(|name| format!("`{}`", name)).collect();
+            error.help(format!("Accepted {}: {}.", what, accepted.join(", ")))
         }
     }
 
+    pub fn missing_param(span: Span, name: &'static str) -> Self {
+        Self::new(ErrorKind::MissingParam(name), span)
+    }
+
+    /// `tooltip` or `help` without a text, on a field without doc comments.
+    pub fn missing_doc(span: Span) -> Self {
+        Self::new(ErrorKind::MissingDoc, span)
+    }
+
+    /// Method of an `#[imgui_ext::actions]` impl block not taking `&mut self`.
+    pub fn receiver(span: Span) -> Self {
+        Self::new(ErrorKind::Receiver, span)
+    }
+
     pub fn invalid_format(span: Span) -> Self {
-        Self {
-            kind: ErrorKind::InvalidFormat,
-            span,
-        }
+        Self::new(ErrorKind::InvalidFormat, span)
     }
 
     /// Multiple annotations per field.
     pub fn multiple(span: Span) -> Self {
-        Self {
-            kind: ErrorKind::Multiple,
-            span,
-        }
+        Self::new(ErrorKind::Multiple, span)
     }
 
     /// No support for anything other that structs with names fields
     pub fn non_struct(span: Span) -> Self {
-        Self {
-            kind: ErrorKind::NonStruct,
-            span,
-        }
+        Self::new(ErrorKind::NonStruct, span)
     }
 
     /// Unexpected annotation mode.
     pub fn unexpected_mode(span: Span) -> Self {
-        Self {
-            kind: ErrorKind::UnexpectedMode,
-            span,
-        }
+        Self::new(ErrorKind::UnexpectedMode, span)
     }
 
-    pub fn unexpected_param(span: Span) -> Self {
-        Self {
-            kind: ErrorKind::UnexpectedParam,
-            span,
-        }
+    /// Annotation not in `accepted`.
+    pub fn unknown_annotation(span: Span, name: &str, accepted: &[&str]) -> Self {
+        Self::new(ErrorKind::UnknownAnnotation(name.to_string()), span).accepted(
+            name,
+            accepted,
+            "annotations",
+        )
+    }
+
+    /// Param not in `accepted`.
+    pub fn unknown_param(span: Span, name: &str, accepted: &[&str]) -> Self {
+        Self::new(ErrorKind::UnknownParam(name.to_string()), span).accepted(
+            name,
+            accepted,
+            "parameters",
+        )
+    }
+
+    /// String that should contain the path to a function (or a type).
+    pub fn invalid_path(span: Span, path: &str) -> Self {
+        Self::new(ErrorKind::InvalidPath(path.to_string()), span)
+            .help("Expected a path, such as `\"function\"` or `\"Self::method\"`.")
+    }
+
+    /// Literal of the wrong type (`what` describes the expected one).
+    pub fn expected(span: Span, what: &'static str) -> Self {
+        Self::new(ErrorKind::Expected(what), span)
     }
 
     pub fn bullet(span: Span) -> Self {
-        Self {
-            kind: ErrorKind::Bullet,
-            span,
-        }
+        Self::new(ErrorKind::Bullet, span)
+    }
+
+    /// `default` param on a widget without a `reset` context menu item.
+    pub fn unused_default(span: Span) -> Self {
+        Self::new(ErrorKind::UnknownParam("default".to_string()), span).help(
+            "The value is restored by the `reset` item of the context menu, enabled with \
+             `#[imgui(context_menu)]` on the struct.",
+        )
     }
 
     /// Annotation param defined already
     pub fn already_defined(span: Span) -> Self {
-        Self {
-            kind: ErrorKind::AlreadyDefined,
-            span,
-        }
+        Self::new(ErrorKind::AlreadyDefined, span)
     }
+}
 
-    pub fn parsing_error(span: Span) -> Self {
-        Self {
-            kind: ErrorKind::ParseError,
-            span,
+/// Closest of the `candidates` to `name` (other than `name` itself), when they
+/// are close enough for `name` to be a typo.
+pub fn suggest<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let max = std::cmp::max(1, name.len() / 3);
+    candidates
+        .iter()
+        .map(|candidate| (distance(name, candidate), *candidate))
+        .filter(|(distance, _)| *distance > 0 && *distance <= max)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+// Edit distance, counting the swap of two adjacent characters as a single edit
+// (optimal string alignment).
+fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
         }
     }
+    d[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{distance, suggest};
+
+    #[test]
+    fn suggestions() {
+        assert_eq!(0, distance("step", "step"));
+        assert_eq!(1, distance("step_fst", "step_fast"));
+        assert_eq!(3, distance("", "max"));
+        assert_eq!(1, distance("edti", "edit"));
+        assert_eq!(
+            Some("step_fast"),
+            suggest("step_fst", &["step", "step_fast"])
+        );
+        assert_eq!(Some("slider"), suggest("sldier", &["slider", "drag"]));
+        assert_eq!(Some("edit"), suggest("edti", &["edit", "picker", "button"]));
+        assert_eq!(None, suggest("foo", &["slider", "drag"]));
+        assert_eq!(None, suggest("step", &["step", "step_fast"]));
+    }
 }
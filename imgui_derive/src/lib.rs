@@ -4,14 +4,17 @@ extern crate proc_macro;
 
 use std::collections::HashSet;
 
-use proc_macro2::TokenStream;
+use proc_macro2::{Literal, TokenStream};
 use quote::quote;
-use syn::{parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput, Fields, Ident};
+use syn::{
+    ext::IdentExt, parse_macro_input, parse_quote, spanned::Spanned, Attribute, AttributeArgs,
+    Data, DeriveInput, Fields, Ident, ItemImpl, Lit, Meta, MetaNameValue, Type, TypeGenerics,
+    WhereClause,
+};
 
 use error::Error;
 
-use crate::error::ErrorKind;
-
+mod actions;
 mod error;
 mod parser;
 
@@ -24,39 +27,295 @@ pub fn ui_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     }
 }
 
+#[proc_macro_attribute]
+pub fn actions(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let args = parse_macro_input!(args as AttributeArgs);
+    let mut input = parse_macro_input!(input as ItemImpl);
+    let output = match actions::impl_actions(&args, &mut input) {
+        Ok(output) => output,
+        Err(error) => error.to_compile_error(),
+    };
+    // the impl block is emitted even on error (without the imgui annotations),
+    // so the errors don't cascade to the uses of its methods.
+    quote!(#input #output).into()
+}
+
 fn impl_derive(input: &DeriveInput) -> Result<TokenStream, Error> {
     let name = &input.ident;
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
 
-    let (body, catch_fields, catch_methods) = match input.data {
-        Data::Struct(ref body) => struct_body(body.fields.clone()),
-        _ => Err(Error::non_struct(input.span())),
-    }?;
+    let container = parser::Container::from_attrs(&input.attrs)?;
+
+    let fields = match &input.data {
+        Data::Struct(body) => match &body.fields {
+            Fields::Unnamed(fields) => return Err(Error::non_struct(fields.span())),
+            fields => fields,
+        },
+        Data::Enum(data) => return Err(Error::non_struct(data.enum_token.span)),
+        Data::Union(data) => return Err(Error::non_struct(data.union_token.span)),
+    };
+
+    let (body, readonly_body, mut catch_fields, mut catch_methods) =
+        struct_body(name, &ty_generics, fields.clone(), &container)?;
+
+    // fields that differ from the baseline, set by `draw_gui_with_baseline`
+    catch_fields.extend(quote! { __dirty: Vec<String>, });
+    catch_methods.extend(quote! {
+        /// Paths of the fields that differ from the baseline, when drawn with
+        /// `UiExt::draw_gui_with_baseline`.
+        pub fn dirty_fields(&self) -> &[String] {
+            &self.__dirty
+        }
+        /// The field at `path` differs from the baseline.
+        pub fn is_dirty(&self, path: &str) -> bool {
+            self.__dirty.iter().any(|dirty| dirty == path)
+        }
+    });
+    let (visit_body, describe_body) = visit_body(fields)?;
+    let (diff_body, revert_body) = diff_body(fields)?;
+
+    // `Visit` and `Diff` recurse into the nested fields, so both impls are
+    // bounded on the types of those fields implementing the traits.
+    let nested = nested_types(fields)?;
+    let visit_where = bounded_where_clause(input, &nested, quote!(imgui_ext::Visit));
+    let diff_where = bounded_where_clause(input, &nested, quote!(imgui_ext::baseline::Diff));
+
+    let menu_bar = |body| match container.menu_bar {
+        Some(parser::MenuBar::Main) => quote! {
+            imgui_ext::menu::main_menu_bar(ui, || { #body });
+        },
+        Some(parser::MenuBar::Window) => quote! {
+            imgui_ext::menu::menu_bar(ui, || { #body });
+        },
+        None => body,
+    };
+    let body = menu_bar(body);
+    let readonly_body = menu_bar(readonly_body);
 
     // crate a new type.
     // It should never generate a collision
-    let event_type = Ident::new(&format!("__{}_Events", name.to_string()), input.span());
+    let event_type = Ident::new(&format!("__{}_Events", name.unraw()), input.span());
+
+    // The events of an `#[imgui_ext::actions]` impl block depend on the generic
+    // params of the struct, so the events type of a generic struct is generic
+    // too in that case.
+    let mut generics = input.generics.clone();
+    let (events_struct, events_type) = if container.impl_actions && !generics.params.is_empty() {
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#name #ty_generics: imgui_ext::Actions));
+        let (events_generics, _, events_where) = generics.split_for_impl();
+        let fields: syn::FieldsNamed = syn::parse2(quote!({ #catch_fields }))?;
+        let idents = fields.named.iter().map(|field| &field.ident);
+        let events_struct = quote! {
+            #[allow(non_camel_case_types)]
+            pub struct #event_type #events_generics #events_where {
+                #catch_fields
+                _marker: std::marker::PhantomData<fn() -> #name #ty_generics>,
+            }
+            impl #events_generics Default for #event_type #ty_generics #events_where {
+                fn default() -> Self {
+                    Self {
+                        #( #idents: Default::default(), )*
+                        _marker: std::marker::PhantomData,
+                    }
+                }
+            }
+            impl #events_generics #event_type #ty_generics #events_where {
+                #catch_methods
+            }
+            impl #events_generics imgui_ext::baseline::DirtyEvents
+                for #event_type #ty_generics #events_where
+            {
+                fn set_dirty(&mut self, dirty: Vec<String>) {
+                    self.__dirty = dirty;
+                }
+            }
+        };
+        (events_struct, quote!(#event_type #ty_generics))
+    } else {
+        let events_struct = quote! {
+            #[allow(non_camel_case_types)]
+            #[derive(Default)]
+            pub struct #event_type {
+                #catch_fields
+            }
+            impl #event_type {
+                #catch_methods
+            }
+            impl imgui_ext::baseline::DirtyEvents for #event_type {
+                fn set_dirty(&mut self, dirty: Vec<String>) {
+                    self.__dirty = dirty;
+                }
+            }
+        };
+        (events_struct, quote!(#event_type))
+    };
+    let gui_where = generics.where_clause.as_ref();
 
     Ok(quote! {
-        #[allow(non_camel_case_types)]
-        pub struct #event_type {
-            #catch_fields
-        }
-        impl #event_type {
-            #catch_methods
-        }
-        impl #impl_generics imgui_ext::Gui for #name #ty_generics #where_clause {
-            type Events = #event_type;
+        #events_struct
+        impl #impl_generics imgui_ext::Gui for #name #ty_generics #gui_where {
+            type Events = #events_type;
             fn draw_gui(ui: &imgui::Ui, ext: &mut Self) -> Self::Events {
-                // Because all fields are bool, it should be OK to zero the memory (right...?)
-                let mut events: Self::Events = unsafe { std::mem::zeroed() };
+                let mut events = Self::Events::default();
                 #body
                 events
             }
+            #[allow(unused_mut, unused_variables)]
+            fn draw_gui_readonly(ui: &imgui::Ui, ext: &Self) -> Self::Events {
+                let mut events = Self::Events::default();
+                #readonly_body
+                events
+            }
+        }
+        impl #impl_generics imgui_ext::Visit for #name #ty_generics #visit_where {
+            #[allow(unused_variables)]
+            fn visit<__V: imgui_ext::visit::Visitor>(ext: &mut Self, visitor: &mut __V) {
+                #visit_body
+            }
+            #[allow(unused_variables)]
+            fn describe<__V: imgui_ext::visit::Visitor>(visitor: &mut __V) {
+                #describe_body
+            }
+        }
+        impl #impl_generics imgui_ext::baseline::Diff for #name #ty_generics #diff_where {
+            #[allow(unused_variables)]
+            fn diff(ext: &Self, base: &Self, path: &str, dirty: &mut Vec<String>) {
+                #diff_body
+            }
+            #[allow(unused_variables)]
+            fn revert(ext: &mut Self, base: &Self, path: &str) -> bool {
+                #revert_body
+                false
+            }
         }
     })
 }
 
+// Types of the fields rendering the UI of their type (see `parser::is_nested`).
+fn nested_types(fields: &Fields) -> Result<Vec<&Type>, Error> {
+    let mut types = Vec::new();
+    for field in fields.iter() {
+        let attr = field.attrs.iter().find(|attr| attr.path.is_ident("imgui"));
+        if let Some(attr) = attr {
+            let tags = attr
+                .parse_meta()
+                .map_err(Error::from)
+                .and_then(parser::parse_meta)?;
+            if tags.iter().any(parser::is_nested) {
+                types.push(&field.ty);
+            }
+        }
+    }
+    Ok(types)
+}
+
+// Where clause of the input, plus a `#ty: #bound` predicate per type.
+//
+// The predicates are higher-ranked, so a concrete type that doesn't implement
+// the trait (a hand-written `Gui`, for example) doesn't fail to compile, but
+// leaves the trait unimplemented instead.
+fn bounded_where_clause(input: &DeriveInput, types: &[&Type], bound: TokenStream) -> WhereClause {
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for ty in types {
+        where_clause
+            .predicates
+            .push(parse_quote!(for<'__bound> #ty: #bound));
+    }
+    where_clause.clone()
+}
+
+// Bodies of the `visit` and `describe` methods of the `Visit` trait. The
+// annotations have been validated by `struct_body` at this point.
+fn visit_body(fields: &Fields) -> Result<(TokenStream, TokenStream), Error> {
+    let mut visit = TokenStream::new();
+    let mut describe = TokenStream::new();
+
+    for field in fields.iter() {
+        let ident = field
+            .ident
+            .clone()
+            .ok_or_else(|| Error::non_struct(field.span()))?;
+        let attr = field.attrs.iter().find(|attr| attr.path.is_ident("imgui"));
+        if let Some(attr) = attr {
+            let tags = attr
+                .parse_meta()
+                .map_err(Error::from)
+                .and_then(parser::parse_meta)?;
+            for tag in tags.iter() {
+                visit.extend(parser::emmit_visit_tokens(&ident, &field.ty, tag, false)?);
+                describe.extend(parser::emmit_visit_tokens(&ident, &field.ty, tag, true)?);
+            }
+        }
+    }
+
+    Ok((visit, describe))
+}
+
+// Bodies of the `diff` and `revert` methods of the `Diff` trait. Fields
+// rendering the UI of their type are compared field by field. The rest are
+// compared (and reverted) as a whole, when they implement `PartialEq` (and
+// `Clone`).
+fn diff_body(fields: &Fields) -> Result<(TokenStream, TokenStream), Error> {
+    let mut diff = TokenStream::new();
+    let mut revert = TokenStream::new();
+
+    for field in fields.iter() {
+        let ident = field
+            .ident
+            .clone()
+            .ok_or_else(|| Error::non_struct(field.span()))?;
+        let attr = field.attrs.iter().find(|attr| attr.path.is_ident("imgui"));
+        if let Some(attr) = attr {
+            let tags = attr
+                .parse_meta()
+                .map_err(Error::from)
+                .and_then(parser::parse_meta)?;
+            let name = Literal::string(&ident.to_string());
+
+            revert.extend(quote! {
+                if path == #name {
+                    #[allow(unused_imports)]
+                    use imgui_ext::baseline::__diff::{CloneRevert, NoRevert, Revert};
+                    return (&&Revert::new(&mut ext.#ident, &base.#ident)).revert();
+                }
+            });
+
+            if tags.iter().any(parser::is_nested) {
+                diff.extend(quote! {
+                    imgui_ext::baseline::Diff::diff(
+                        &ext.#ident,
+                        &base.#ident,
+                        &imgui_ext::baseline::__diff::join(path, #name),
+                        dirty,
+                    );
+                });
+                revert.extend(quote! {
+                    if let Some(path) = imgui_ext::baseline::__diff::strip(path, #name) {
+                        return imgui_ext::baseline::Diff::revert(&mut ext.#ident, &base.#ident, path);
+                    }
+                });
+            } else {
+                diff.extend(quote! {{
+                    #[allow(unused_imports)]
+                    use imgui_ext::baseline::__diff::{Field, NoPartialEq, PartialEqField};
+                    if (&&Field(&ext.#ident, &base.#ident)).differs() {
+                        dirty.push(imgui_ext::baseline::__diff::join(path, #name));
+                    }
+                }});
+            }
+        }
+    }
+
+    Ok((diff, revert))
+}
+
 // Adds support to allow multiple imgui tags in a single field:
 // ```
 // struct Demo {
@@ -69,22 +328,31 @@ fn impl_derive(input: &DeriveInput) -> Result<TokenStream, Error> {
 //     #[imgui(input(...))]
 //     y: f32,
 // }
-fn struct_body(fields: Fields) -> Result<(TokenStream, TokenStream, TokenStream), Error> {
+fn struct_body(
+    name: &Ident,
+    ty_generics: &TypeGenerics,
+    fields: Fields,
+    container: &parser::Container,
+) -> Result<(TokenStream, TokenStream, TokenStream, TokenStream), Error> {
     let mut input_methods: TokenStream = TokenStream::new();
 
     let mut input_fields: TokenStream = TokenStream::new();
-    let mut input_fields_set = HashSet::new();
+    // methods of the dirty fields (see `events_type`)
+    let mut input_fields_set: HashSet<_> = ["dirty_fields", "is_dirty"]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
 
     let field_body = fields
         .iter()
         .enumerate()
         .flat_map(|(_, field)| {
-            // TODO support for unnamed attributes
-            let ident = field
-                .ident
-                .clone()
-                .expect("Unnamed fields not yet supported.");
+            let ident = match field.ident.clone() {
+                Some(ident) => ident,
+                None => return vec![Err(Error::non_struct(field.span()))],
+            };
             let ty = &field.ty;
+            let doc = doc_comment(&field.attrs);
 
             // collect all the imgui attributes
             // we need to check that there is only one.
@@ -105,36 +373,107 @@ fn struct_body(fields: Fields) -> Result<(TokenStream, TokenStream, TokenStream)
             match (first, second) {
                 // No annotations were found.
                 // Emmit no sourcecode.
-                (None, None) => vec![Ok(TokenStream::new())],
+                (None, None) => vec![Ok((TokenStream::new(), TokenStream::new()))],
 
                 // There is more than one imgui annotation.
                 // Raise a descriptive error pointing to the extra annotation.
-                (Some(_), Some(err)) => vec![Err(Error::multiple(err.span()))],
+                (Some(_), Some(err)) => vec![Err(Error::multiple(err.path.span()))],
 
                 // There is a single annotation, as it should.
                 // Parse the annotation and emmit the source code for this field
                 (Some(attr), None) => {
                     let tags = attr
                         .parse_meta() // -> Meta
-                        .map_err(|_| Error::new(ErrorKind::ParseError, attr.span()))
+                        .map_err(Error::from)
                         .and_then(parser::parse_meta); // -> Result<Vec<Tag>>
 
-                    match tags {
-                        Err(error) => vec![Err(error)],
-                        Ok(tags) => tags
-                            .into_iter()
+                    // doc comments enabled on the container
+                    let tags = match (container.docs, doc.as_ref()) {
+                        (Some(parser::Docs::Tooltip), Some(_)) => tags.map(|tags| {
+                            tags.into_iter()
+                                .map(|tag| tag.with_doc_tooltip(attr.span()))
+                                .collect()
+                        }),
+                        _ => tags,
+                    };
+                    let tags = match container.context_menu.as_ref() {
+                        Some(menu) => tags.map(|tags| {
+                            tags.into_iter()
+                                .map(|tag| tag.with_context_menu(menu))
+                                .collect()
+                        }),
+                        None => tags,
+                    };
+                    let verbose = match (container.docs, doc.as_ref()) {
+                        (Some(parser::Docs::Verbose), Some(doc)) => {
+                            let doc = Literal::string(doc);
+                            let tokens = quote!({
+                                ui.text_wrapped(imgui::im_str!(#doc));
+                            });
+                            Some(Ok((tokens.clone(), tokens)))
+                        }
+                        _ => None,
+                    };
+
+                    // used to highlight (or filter out) the field, and to report
+                    // the annotation to the testing harness along with the first
+                    // widget
+                    let field = Literal::string(&ident.to_string());
+                    let source = Literal::string(&parser::annotation_source(&attr));
+
+                    let body = tags.and_then(|tags| {
+                        tags.into_iter()
                             .map(|tag| {
-                                parser::emmit_tag_tokens(
-                                    &ident,
-                                    &ty,
-                                    &attr,
+                                let ctx = parser::Context {
+                                    ident: &ident,
+                                    ty: &ty,
+                                    attr: &attr,
+                                    doc: doc.as_deref(),
+                                    mode: parser::Mode::Edit,
+                                    header: None,
+                                };
+                                let edit = parser::emmit_tag_tokens(
+                                    ctx,
                                     &tag,
                                     &mut input_fields,
                                     &mut input_methods,
                                     &mut input_fields_set,
-                                )
+                                )?;
+                                // the events are declared by the first pass
+                                let readonly = parser::emmit_tag_tokens(
+                                    parser::Context {
+                                        mode: parser::Mode::ReadOnly,
+                                        ..ctx
+                                    },
+                                    &tag,
+                                    &mut TokenStream::new(),
+                                    &mut TokenStream::new(),
+                                    &mut HashSet::new(),
+                                )?;
+                                Ok((edit, readonly))
                             })
-                            .collect(),
+                            .chain(verbose)
+                            .collect::<Result<Vec<_>, Error>>()
+                    });
+
+                    match body {
+                        Err(error) => vec![Err(error)],
+                        Ok(body) => {
+                            let (edit, readonly): (Vec<_>, Vec<_>) = body.into_iter().unzip();
+                            let edit = quote! {{
+                                if imgui_ext::__begin_field(ui, #field, #source) {
+                                    #( #edit; )*
+                                }
+                                imgui_ext::__end_field(ui);
+                            }};
+                            let readonly = quote! {{
+                                if imgui_ext::__begin_field(ui, #field, #source) {
+                                    #( #readonly; )*
+                                }
+                                imgui_ext::__end_field(ui);
+                            }};
+                            vec![Ok((edit, readonly))]
+                        }
                     }
                 }
 
@@ -143,5 +482,78 @@ fn struct_body(fields: Fields) -> Result<(TokenStream, TokenStream, TokenStream)
         })
         .collect::<Result<Vec<_>, Error>>()?;
 
-    Ok((quote! { #( #field_body );*}, input_fields, input_methods))
+    let (mut field_body, mut readonly_body): (Vec<_>, Vec<_>) = field_body.into_iter().unzip();
+
+    if !container.actions.is_empty() {
+        field_body.push(parser::emmit_actions(
+            &container.actions,
+            &mut input_fields,
+            &mut input_methods,
+            &mut input_fields_set,
+            None,
+            parser::Mode::Edit,
+        )?);
+        readonly_body.push(parser::emmit_actions(
+            &container.actions,
+            &mut TokenStream::new(),
+            &mut TokenStream::new(),
+            &mut HashSet::new(),
+            None,
+            parser::Mode::ReadOnly,
+        )?);
+    }
+
+    if container.impl_actions {
+        let ident = Ident::new("actions", name.span());
+        if !input_fields_set.insert(ident.to_string()) {
+            return Err(Error::already_defined(ident.span()));
+        }
+        input_fields
+            .extend(quote! { pub #ident: <#name #ty_generics as imgui_ext::Actions>::Events , });
+        input_methods.extend(quote! {
+            pub fn #ident(&self) -> &<#name #ty_generics as imgui_ext::Actions>::Events { &self.#ident }
+        });
+        field_body.push(quote! {{
+            events.#ident = imgui_ext::Actions::draw_actions(ui, ext);
+        }});
+        readonly_body.push(quote! {{
+            events.#ident = imgui_ext::Actions::draw_actions_readonly(ui, ext);
+        }});
+    }
+
+    Ok((
+        quote! { #( #field_body; )* },
+        quote! { #( #readonly_body; )* },
+        input_fields,
+        input_methods,
+    ))
+}
+
+// Collects the `///` comments of a field into a single string.
+// Lines are joined with spaces, and blank lines are kept as line breaks.
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let mut doc: Option<String> = None;
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("doc")) {
+        let line = match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(line),
+                ..
+            })) => line.value(),
+            _ => continue,
+        };
+        let line = line.trim();
+
+        match doc.as_mut() {
+            None => doc = Some(line.to_string()),
+            Some(doc) if line.is_empty() => doc.push('\n'),
+            Some(doc) if doc.ends_with('\n') => doc.push_str(line),
+            Some(doc) => {
+                doc.push(' ');
+                doc.push_str(line);
+            }
+        }
+    }
+
+    doc.map(|doc| doc.trim().to_string())
 }
@@ -1,13 +1,18 @@
 use std::collections::HashSet;
 use std::string::ToString;
 
-use proc_macro2::{Literal, TokenStream};
+use proc_macro2::{Delimiter, Literal, Spacing, Span, TokenStream, TokenTree};
 use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{Attribute, Ident, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Path, Type};
+use syn::{Attribute, Ident, Lit, LitStr, Meta, MetaList, MetaNameValue, NestedMeta, Path, Type};
 
 use super::error::Error;
 
+// Unknown params of the annotations of fields are reported with `unknown_param`
+// (which lists the common params too). Other lists of params (such as the ones
+// of the container attributes) start with `@unknown(Error::unknown_param)`.
 macro_rules! tag {
     (
         $(#[$meta:meta])*
@@ -15,6 +20,23 @@ macro_rules! tag {
             fields { $( $field:ident : Lit ,)* },
             optional { $( $opt_field:ident : Option<Lit> ,)* }
         }
+    ) => {
+        tag! {
+            @unknown(unknown_param)
+            $(#[$meta])*
+            pub struct $tag {
+                fields { $( $field : Lit ,)* },
+                optional { $( $opt_field : Option<Lit> ,)* }
+            }
+        }
+    };
+    (
+        @unknown($unknown:path)
+        $(#[$meta:meta])*
+        pub struct $tag:ident {
+            fields { $( $field:ident : Lit ,)* },
+            optional { $( $opt_field:ident : Option<Lit> ,)* }
+        }
     ) => {
         $(#[$meta])*
         pub struct $tag {
@@ -28,7 +50,7 @@ macro_rules! tag {
                 for param in list.nested.iter() {
                     match param {
                         NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) => {
-                            let ident = path_to_ident(&path);
+                            let ident = path_to_ident(&path)?;
                             match ident.to_string().as_str() {
                                 //"label" => widget.label = Some(lit.clone()),
                                 $( stringify!($opt_field) => {
@@ -43,28 +65,45 @@ macro_rules! tag {
                                     }
                                     $field = Some(lit.clone());
                                 },)*
-                                _ => return Err(Error::unexpected_param(ident.span())),
+                                name => return Err($unknown(
+                                    ident.span(),
+                                    name,
+                                    &[$( stringify!($field), )* $( stringify!($opt_field), )*],
+                                )),
                             }
                         }
-                        // TODO use proper span
-                        _ => return Err(Error::invalid_format(list.span())),
+                        _ => return Err(Error::invalid_format(param.span())
+                            .help("Parameters are written as `name = value`.")),
                     }
                 }
                 Ok(Self {
-                    //$( $field : $field.ok_or(Error::new(list.span(), format!("Parameter `{}` missing.", stringify!($field) )))?,)*
-                    $( $field : $field.ok_or(Error::missing_param(list.span(), stringify!($field)))?,)*
+                    $( $field : $field.ok_or_else(|| Error::missing_param(list.path.span(), stringify!($field)))?,)*
                     $( $opt_field,)*
                 })
             }
+
+            /// Params defined in the annotation, by name.
+            #[allow(dead_code, unused_mut)]
+            fn params(&self) -> Vec<(&'static str, &Lit)> {
+                let mut params = vec![$( (stringify!($field), &self.$field), )*];
+                $( params.extend(self.$opt_field.iter().map(|lit| (stringify!($opt_field), lit))); )*
+                params
+            }
         }
 
     }
 }
 
-fn path_to_ident(path: &Path) -> &Ident {
-    let segments = &path.segments;
-    assert_eq!(1, segments.len());
-    &segments.first().unwrap().ident
+/// Param of a field annotation not in `accepted`, nor one of the common params.
+fn unknown_param(span: Span, name: &str, accepted: &[&str]) -> Error {
+    let accepted: Vec<_> = accepted.iter().chain(Common::PARAMS).cloned().collect();
+    Error::unknown_param(span, name, &accepted)
+}
+
+/// Names in annotations are plain identifiers (`foo`, not `foo::bar`).
+fn path_to_ident(path: &Path) -> Result<&Ident, Error> {
+    path.get_ident()
+        .ok_or_else(|| Error::expected(path.span(), "an identifier"))
 }
 
 pub enum DisplayParam {
@@ -102,26 +141,39 @@ impl Display {
                 }
 
                 (State::Display, NestedMeta::Meta(Meta::Path(path))) => {
-                    let ident = path_to_ident(&path);
+                    let ident = path_to_ident(&path)?;
                     display.params.push(DisplayParam::Ident(ident.clone()));
                 }
 
                 (
                     State::Init,
                     NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })),
-                ) if path_to_ident(&path).to_string() == "label" => {
+                ) if path.is_ident("label") => {
                     display.label = Some(lit.clone());
                 }
 
                 (
                     State::Init,
                     NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })),
-                ) if path_to_ident(&path).to_string() == "display" => {
+                ) if path.is_ident("display") => {
                     display.display = Some(lit.clone());
                     state = State::Display;
                 }
 
-                _ => return Err(Error::invalid_format(params.span())),
+                (State::Init, NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, .. }))) => {
+                    let ident = path_to_ident(path)?;
+                    return Err(unknown_param(
+                        ident.span(),
+                        &ident.to_string(),
+                        &["label", "display"],
+                    ));
+                }
+
+                _ => {
+                    return Err(Error::invalid_format(attr.span()).help(
+                        "Expected `display(label = \"...\", display = \"...\", fields...)`.",
+                    ))
+                }
             }
         }
 
@@ -236,6 +288,35 @@ tag! {
     }
 }
 
+tag! {
+    /// `#[imgui(menu(label = "..."))]`
+    #[derive(Default)]
+    pub struct Menu {
+        fields {
+        },
+        optional {
+            label: Option<Lit>,
+            catch: Option<Lit>,
+            map: Option<Lit>,
+        }
+    }
+}
+
+tag! {
+    /// `#[imgui(menu_item(label = "...", shortcut = "..."))]`
+    #[derive(Default)]
+    pub struct MenuItem {
+        fields {
+        },
+        optional {
+            label: Option<Lit>,
+            shortcut: Option<Lit>,
+            catch: Option<Lit>,
+            map: Option<Lit>,
+        }
+    }
+}
+
 tag! {
     #[derive(Default)]
     pub struct Progress {
@@ -383,7 +464,7 @@ impl Vars {
         for meta in list.nested.iter() {
             match meta {
                 NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) => {
-                    let ident = path_to_ident(&path);
+                    let ident = path_to_ident(&path)?;
                     match &ident.to_string()[..] {
                         "color" => {
                             if color.is_some() {
@@ -401,13 +482,17 @@ impl Vars {
                             }
                         }
 
-                        _ => return Err(Error::unexpected_param(ident.span())),
+                        name => {
+                            return Err(unknown_param(
+                                ident.span(),
+                                name,
+                                &["style", "color", "content"],
+                            ))
+                        }
                     }
                 }
 
-                NestedMeta::Meta(Meta::List(list))
-                    if path_to_ident(&list.path).to_string() == "content" =>
-                {
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("content") => {
                     if content.is_some() {
                         return Err(Error::already_defined(list.span()));
                     } else {
@@ -415,8 +500,7 @@ impl Vars {
                     }
                 }
 
-                // Nope
-                _ => return Err(Error::invalid_format(list.span())),
+                _ => return Err(Error::invalid_format(meta.span())),
             }
         }
 
@@ -451,7 +535,7 @@ impl Tree {
             match meta {
                 // label = "..."
                 NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) => {
-                    let ident = path_to_ident(&path);
+                    let ident = path_to_ident(&path)?;
                     match &ident.to_string()[..] {
                         "label" => {
                             if label.is_some() {
@@ -477,15 +561,19 @@ impl Tree {
                             }
                         }
 
-                        _ => return Err(Error::unexpected_param(ident.span())),
+                        name => {
+                            return Err(unknown_param(
+                                ident.span(),
+                                name,
+                                &["label", "flags", "cond", "node"],
+                            ))
+                        }
                     }
                 }
 
                 // node(...)
                 // we need to validate that the nested list contains a single item.
-                NestedMeta::Meta(Meta::List(list))
-                    if path_to_ident(&list.path).to_string() == "node" =>
-                {
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("node") => {
                     if node.is_some() {
                         return Err(Error::already_defined(list.span()));
                     } else {
@@ -493,9 +581,7 @@ impl Tree {
                     }
                 }
 
-                // Nope
-                _ => panic!(),
-                //_ => return Err(Error::new(list.span(), "fuck")),
+                _ => return Err(Error::invalid_format(meta.span())),
             }
         }
 
@@ -508,6 +594,864 @@ impl Tree {
     }
 }
 
+/// Render the contents inside of a child window (scrolling region).
+/// - `#[imgui(child(label = "...", size = "...", border = true, flags = "...", content(...)))]`
+#[derive(Default)]
+pub struct Child {
+    /// Child window ID. Defaults to the field identifier.
+    label: Option<Lit>,
+    /// An identifier to a local function returning the size of the child window.
+    size: Option<Lit>,
+    /// Draw a border around the child window.
+    border: Option<Lit>,
+    /// An identifier to a local function returning the window flags.
+    flags: Option<Lit>,
+    /// List of ui widgets rendered inside of the child window.
+    content: Option<Vec<Tag>>,
+}
+
+impl Child {
+    fn from_meta_list(list: &MetaList) -> Result<Self, Error> {
+        let mut child = Child::default();
+
+        for meta in list.nested.iter() {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) => {
+                    let ident = path_to_ident(&path)?;
+                    let param = match &ident.to_string()[..] {
+                        "label" => &mut child.label,
+                        "size" => &mut child.size,
+                        "border" => &mut child.border,
+                        "flags" => &mut child.flags,
+                        name => {
+                            return Err(unknown_param(
+                                ident.span(),
+                                name,
+                                &["label", "size", "border", "flags", "content"],
+                            ))
+                        }
+                    };
+                    if param.is_some() {
+                        return Err(Error::already_defined(ident.span()));
+                    }
+                    *param = Some(lit.clone());
+                }
+
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("content") => {
+                    if child.content.is_some() {
+                        return Err(Error::already_defined(list.span()));
+                    } else {
+                        child.content = Some(parse_meta_list(&list)?);
+                    }
+                }
+
+                _ => return Err(Error::invalid_format(meta.span())),
+            }
+        }
+
+        Ok(child)
+    }
+}
+
+/// How doc comments are shown, when enabled on the container:
+/// - `#[imgui(docs)]` or `#[imgui(docs = "tooltip")]`
+/// - `#[imgui(docs = "verbose")]`
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Docs {
+    /// Show the doc comment of a field when its widgets are hovered.
+    Tooltip,
+    /// Show the doc comment of a field as wrapped text, below its widgets.
+    Verbose,
+}
+
+/// Menu bar rendered by the container:
+/// - `#[imgui(main_menu_bar)]` at the top of the screen.
+/// - `#[imgui(menu_bar)]` at the top of the current window.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum MenuBar {
+    Main,
+    Window,
+}
+
+tag! {
+    @unknown(Error::unknown_param)
+    /// `method(label = "...", confirm = "...")` in an `actions(...)` attribute.
+    pub struct ActionParams {
+        fields {
+        },
+        optional {
+            label: Option<Lit>,
+            confirm: Option<Lit>,
+        }
+    }
+}
+
+/// Button that calls a method of the struct, from the `actions(...)` attribute:
+/// - `actions(method = "Label")`
+/// - `actions(method(label = "Label", confirm = "Are you sure?"))`
+pub struct Action {
+    /// Method taking `&mut self`. Also the name of the input event.
+    method: Ident,
+    /// Label of the button. Defaults to the method name.
+    label: Option<Lit>,
+    /// Text of the modal popup shown to confirm the action.
+    confirm: Option<Lit>,
+    /// Arguments of the method (after `&mut self`), which get an input widget.
+    /// Only methods from `#[imgui_ext::actions]` impl blocks take arguments.
+    args: Vec<(Ident, Type)>,
+}
+
+impl Action {
+    fn from_meta_list(list: &MetaList) -> Result<Vec<Self>, Error> {
+        let mut actions = Vec::new();
+        for nested in list.nested.iter() {
+            let action = match nested {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) => Action {
+                    method: path_to_ident(path)?.clone(),
+                    label: Some(lit.clone()),
+                    confirm: None,
+                    args: Vec::new(),
+                },
+                NestedMeta::Meta(Meta::List(list)) => {
+                    let ActionParams { label, confirm } = ActionParams::from_meta_list(list)?;
+                    Action {
+                        method: path_to_ident(&list.path)?.clone(),
+                        label,
+                        confirm,
+                        args: Vec::new(),
+                    }
+                }
+                NestedMeta::Meta(Meta::Path(path)) => Action {
+                    method: path_to_ident(path)?.clone(),
+                    label: None,
+                    confirm: None,
+                    args: Vec::new(),
+                },
+                NestedMeta::Lit(lit) => return Err(Error::invalid_format(lit.span())),
+            };
+            if actions.iter().any(|a: &Action| a.method == action.method) {
+                return Err(Error::already_defined(action.method.span()));
+            }
+            actions.push(action);
+        }
+        Ok(actions)
+    }
+
+    /// Action of a method annotated with `#[imgui(button)]` or
+    /// `#[imgui(button(label = "...", confirm = "..."))]`.
+    pub fn from_method(
+        attr: &Attribute,
+        method: Ident,
+        args: Vec<(Ident, Type)>,
+    ) -> Result<Self, Error> {
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            Ok(meta) => return Err(Error::invalid_format(meta.span())),
+            Err(err) => return Err(err.into()),
+        };
+        let mut nested = list.nested.iter();
+        let (label, confirm) = match (nested.next(), nested.next()) {
+            (Some(NestedMeta::Meta(Meta::Path(path))), None) if path.is_ident("button") => {
+                (None, None)
+            }
+            (Some(NestedMeta::Meta(Meta::List(list))), None) if list.path.is_ident("button") => {
+                let ActionParams { label, confirm } = ActionParams::from_meta_list(list)?;
+                (label, confirm)
+            }
+            (Some(NestedMeta::Meta(meta)), None) => {
+                let ident = path_to_ident(meta.path())?;
+                return Err(Error::unknown_annotation(
+                    ident.span(),
+                    &ident.to_string(),
+                    &["button"],
+                ));
+            }
+            _ => return Err(Error::invalid_format(list.span())),
+        };
+        Ok(Action {
+            method,
+            label,
+            confirm,
+            args,
+        })
+    }
+
+    /// Tuple with the types of the arguments of the method, if it takes any.
+    pub fn args_type(&self) -> Option<TokenStream> {
+        if self.args.is_empty() {
+            return None;
+        }
+        let types = self.args.iter().map(|(_, ty)| ty);
+        Some(quote!(( #( #types , )* )))
+    }
+}
+
+/// Annotations on the struct itself, rather than on its fields.
+#[derive(Default)]
+pub struct Container {
+    pub docs: Option<Docs>,
+    pub context_menu: Option<ContextMenu>,
+    pub menu_bar: Option<MenuBar>,
+    pub actions: Vec<Action>,
+    /// Draw the buttons of the `#[imgui_ext::actions]` impl block of the struct.
+    pub impl_actions: bool,
+}
+
+impl Container {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self, Error> {
+        let mut container = Container::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("imgui")) {
+            let list = match attr.parse_meta() {
+                Ok(Meta::List(list)) => list,
+                Ok(meta) => return Err(Error::invalid_format(meta.span())),
+                Err(err) => return Err(err.into()),
+            };
+
+            for nested in list.nested.iter() {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("docs") => {
+                        if container.docs.is_some() {
+                            return Err(Error::already_defined(path.span()));
+                        }
+                        container.docs = Some(Docs::Tooltip);
+                    }
+                    NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. }))
+                        if path.is_ident("docs") =>
+                    {
+                        if container.docs.is_some() {
+                            return Err(Error::already_defined(path.span()));
+                        }
+                        container.docs = match lit {
+                            Lit::Str(s) if s.value() == "tooltip" => Some(Docs::Tooltip),
+                            Lit::Str(s) if s.value() == "verbose" => Some(Docs::Verbose),
+                            _ => return Err(Error::invalid_format(lit.span())),
+                        };
+                    }
+                    NestedMeta::Meta(Meta::Path(path))
+                        if path.is_ident("main_menu_bar") || path.is_ident("menu_bar") =>
+                    {
+                        if container.menu_bar.is_some() {
+                            return Err(Error::already_defined(path.span()));
+                        }
+                        container.menu_bar = if path.is_ident("main_menu_bar") {
+                            Some(MenuBar::Main)
+                        } else {
+                            Some(MenuBar::Window)
+                        };
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("context_menu") => {
+                        if container.context_menu.is_some() {
+                            return Err(Error::already_defined(path.span()));
+                        }
+                        container.context_menu = Some(ContextMenu::builtin());
+                    }
+                    NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("context_menu") => {
+                        if container.context_menu.is_some() {
+                            return Err(Error::already_defined(list.path.span()));
+                        }
+                        container.context_menu = Some(ContextMenu::from_meta_list(list)?);
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("actions") => {
+                        if container.impl_actions {
+                            return Err(Error::already_defined(path.span()));
+                        }
+                        container.impl_actions = true;
+                    }
+                    NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("actions") => {
+                        if !container.actions.is_empty() {
+                            return Err(Error::already_defined(list.path.span()));
+                        }
+                        container.actions = Action::from_meta_list(list)?;
+                    }
+                    NestedMeta::Meta(meta) => {
+                        let ident = path_to_ident(meta.path())?;
+                        return Err(Error::unknown_param(
+                            ident.span(),
+                            &ident.to_string(),
+                            &[
+                                "docs",
+                                "main_menu_bar",
+                                "menu_bar",
+                                "context_menu",
+                                "actions",
+                            ],
+                        ));
+                    }
+                    NestedMeta::Lit(lit) => return Err(Error::invalid_format(lit.span())),
+                }
+            }
+        }
+
+        Ok(container)
+    }
+}
+
+/// Popup window, opened by an input event:
+/// - `#[imgui(popup(label = "...", open = "...", content(...)))]`
+/// - `#[imgui(modal(label = "...", open = "...", ok = "...", cancel = "...", content(...)))]`
+#[derive(Default)]
+pub struct Popup {
+    /// Render a modal popup (blocks the interaction with the rest of the UI).
+    modal: bool,
+    /// Popup title & ID. Defaults to the field identifier.
+    label: Option<Lit>,
+    /// Name of the (bool) input event that opens the popup.
+    open: Option<Lit>,
+    /// Label of the OK button.
+    ok: Option<Lit>,
+    /// Label of the Cancel button.
+    cancel: Option<Lit>,
+    /// Prefix of the `_ok` and `_cancel` input events.
+    catch: Option<Lit>,
+    /// List of ui widgets rendered inside of the popup.
+    content: Option<Vec<Tag>>,
+}
+
+impl Popup {
+    fn from_meta_list(list: &MetaList, modal: bool) -> Result<Self, Error> {
+        let mut popup = Popup {
+            modal,
+            ..Default::default()
+        };
+
+        for meta in list.nested.iter() {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) => {
+                    let ident = path_to_ident(&path)?;
+                    let param = match &ident.to_string()[..] {
+                        "label" => &mut popup.label,
+                        "open" => &mut popup.open,
+                        "ok" => &mut popup.ok,
+                        "cancel" => &mut popup.cancel,
+                        "catch" => &mut popup.catch,
+                        name => {
+                            return Err(unknown_param(
+                                ident.span(),
+                                name,
+                                &["label", "open", "ok", "cancel", "catch", "content"],
+                            ))
+                        }
+                    };
+                    if param.is_some() {
+                        return Err(Error::already_defined(ident.span()));
+                    }
+                    *param = Some(lit.clone());
+                }
+
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("content") => {
+                    if popup.content.is_some() {
+                        return Err(Error::already_defined(list.span()));
+                    } else {
+                        popup.content = Some(parse_meta_list(&list)?);
+                    }
+                }
+
+                _ => return Err(Error::invalid_format(meta.span())),
+            }
+        }
+
+        if popup.open.is_none() {
+            return Err(Error::missing_param(list.span(), "open"));
+        }
+
+        Ok(popup)
+    }
+}
+
+/// Context menu of the widgets, opened with a right click:
+/// - `#[imgui(context_menu)]` all the builtin items.
+/// - `#[imgui(context_menu(reset, copy, paste, item(label = "...", call = "...")))]`
+#[derive(Clone, Default)]
+pub struct ContextMenu {
+    /// "Reset to default" item.
+    reset: bool,
+    /// "Copy value" item.
+    copy: bool,
+    /// "Paste value" item.
+    paste: bool,
+    /// User defined items (label and path to a method on `Self`).
+    items: Vec<(Lit, Lit)>,
+}
+
+impl ContextMenu {
+    /// `context_menu` on its own (all the builtin items).
+    fn builtin() -> Self {
+        ContextMenu {
+            reset: true,
+            copy: true,
+            paste: true,
+            items: Vec::new(),
+        }
+    }
+
+    fn from_meta_list(list: &MetaList) -> Result<Self, Error> {
+        let mut menu = ContextMenu::default();
+
+        for nested in list.nested.iter() {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) => {
+                    let item = match &path_to_ident(path)?.to_string()[..] {
+                        "reset" => &mut menu.reset,
+                        "copy" => &mut menu.copy,
+                        "paste" => &mut menu.paste,
+                        name => {
+                            return Err(Error::unknown_param(
+                                path.span(),
+                                name,
+                                &["reset", "copy", "paste", "item"],
+                            ))
+                        }
+                    };
+                    if *item {
+                        return Err(Error::already_defined(path.span()));
+                    }
+                    *item = true;
+                }
+                NestedMeta::Meta(Meta::List(item)) if item.path.is_ident("item") => {
+                    let item = ContextMenuItem::from_meta_list(item)?;
+                    menu.items.push((item.label, item.call));
+                }
+                _ => return Err(Error::invalid_format(nested.span())),
+            }
+        }
+
+        Ok(menu)
+    }
+}
+
+tag! {
+    @unknown(Error::unknown_param)
+    /// `item(label = "...", call = "...")` in a `context_menu(...)`
+    pub struct ContextMenuItem {
+        fields {
+            label: Lit,
+            call: Lit,
+        },
+        optional {
+        }
+    }
+}
+
+/// Text of a `tooltip` or `help` param.
+pub enum Hint {
+    /// `tooltip = "..."`
+    Lit(Lit),
+    /// `tooltip` on its own takes the text from the field's doc comment.
+    Doc(Span),
+}
+
+impl Hint {
+    fn text(&self, doc: Option<&str>) -> Result<Literal, Error> {
+        match self {
+            Hint::Lit(Lit::Str(text)) => Ok(Literal::string(&text.value())),
+            Hint::Lit(lit) => Err(Error::invalid_format(lit.span())),
+            Hint::Doc(span) => doc
+                .map(Literal::string)
+                .ok_or_else(|| Error::missing_doc(*span)),
+        }
+    }
+}
+
+/// Params that are accepted by every annotation:
+/// - `tooltip = "..."` text shown when the widget is hovered.
+/// - `help = "..."` places a `(?)` marker next to the widget.
+/// - `default = ...` value restored by the "Reset to default" context menu item.
+/// - `readonly` renders the widget in read-only mode.
+/// - `disabled_if = "method"` renders the widget in read-only mode when the
+///   method returns `true`.
+/// - `enabled_if = "path"` renders the widget in read-only mode unless the
+///   condition is `true`.
+/// - `visible_if = "path"` skips the widget unless the condition is `true`.
+/// - `clamp(min, max)` clamps the value after a change.
+/// - `validate = "path"` shows an error below the widget when the value is not
+///   valid.
+/// - `on_change = "..."` method (or path to a function) called after a (valid)
+///   change.
+/// - `on_click = "..."` method (or path to a function) called when a button is
+///   clicked.
+/// - `context_menu` or `context_menu(...)` context menu of the widget (same
+///   as the container annotation, for a single widget).
+#[derive(Default)]
+pub struct Common {
+    tooltip: Option<Hint>,
+    help: Option<Hint>,
+    default: Option<Lit>,
+    readonly: bool,
+    disabled_if: Option<Lit>,
+    enabled_if: Option<Lit>,
+    visible_if: Option<Lit>,
+    clamp: Option<(Lit, Lit)>,
+    validate: Option<Lit>,
+    on_change: Option<Lit>,
+    on_click: Option<Lit>,
+    /// Set from the param, or from the container annotation
+    /// (`#[imgui(context_menu(...))]`).
+    context_menu: Option<ContextMenu>,
+}
+
+impl Common {
+    /// Names of the common params, accepted by every annotation.
+    const PARAMS: &'static [&'static str] = &[
+        "tooltip",
+        "help",
+        "default",
+        "readonly",
+        "disabled_if",
+        "enabled_if",
+        "visible_if",
+        "clamp",
+        "validate",
+        "on_change",
+        "on_click",
+        "context_menu",
+    ];
+
+    /// Takes the common params out of an annotation. The remaining params are
+    /// returned in a new list so the annotation can be parsed as usual.
+    fn split(list: &MetaList) -> Result<(MetaList, Self), Error> {
+        let mut common = Common::default();
+        let mut rest = list.clone();
+        rest.nested = Punctuated::new();
+        // identifiers following `display(display = "...", ...)` are the fields
+        // of the formatted text, not params of the annotation
+        let mut format_args = false;
+
+        for nested in list.nested.iter() {
+            match nested {
+                NestedMeta::Meta(Meta::Path(_)) if format_args => {
+                    rest.nested.push(nested.clone());
+                    continue;
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, .. }))
+                    if list.path.is_ident("display") && path.is_ident("display") =>
+                {
+                    format_args = true;
+                    rest.nested.push(nested.clone());
+                    continue;
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) => {
+                    let param = if path.is_ident("default") {
+                        Some(&mut common.default)
+                    } else if path.is_ident("disabled_if") {
+                        Some(&mut common.disabled_if)
+                    } else if path.is_ident("enabled_if") {
+                        Some(&mut common.enabled_if)
+                    } else if path.is_ident("visible_if") {
+                        Some(&mut common.visible_if)
+                    } else if path.is_ident("validate") {
+                        Some(&mut common.validate)
+                    } else if path.is_ident("on_change") {
+                        Some(&mut common.on_change)
+                    } else if path.is_ident("on_click") {
+                        Some(&mut common.on_click)
+                    } else {
+                        None
+                    };
+                    if let Some(param) = param {
+                        if param.is_some() {
+                            return Err(Error::already_defined(path.span()));
+                        }
+                        *param = Some(lit.clone());
+                        continue;
+                    }
+                }
+                NestedMeta::Meta(Meta::List(clamp)) if clamp.path.is_ident("clamp") => {
+                    if common.clamp.is_some() {
+                        return Err(Error::already_defined(clamp.span()));
+                    }
+                    let mut range = clamp.nested.iter();
+                    match (range.next(), range.next(), range.next()) {
+                        (Some(NestedMeta::Lit(min)), Some(NestedMeta::Lit(max)), None) => {
+                            common.clamp = Some((min.clone(), max.clone()));
+                        }
+                        _ => return Err(Error::invalid_format(clamp.span())),
+                    }
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("readonly") => {
+                    if common.readonly {
+                        return Err(Error::already_defined(path.span()));
+                    }
+                    common.readonly = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("context_menu") => {
+                    if common.context_menu.is_some() {
+                        return Err(Error::already_defined(path.span()));
+                    }
+                    common.context_menu = Some(ContextMenu::builtin());
+                    continue;
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("context_menu") => {
+                    if common.context_menu.is_some() {
+                        return Err(Error::already_defined(list.path.span()));
+                    }
+                    common.context_menu = Some(ContextMenu::from_meta_list(list)?);
+                    continue;
+                }
+                _ => {}
+            }
+
+            let (path, hint) = match nested {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) => {
+                    (path, Hint::Lit(lit.clone()))
+                }
+                NestedMeta::Meta(Meta::Path(path)) => (path, Hint::Doc(path.span())),
+                _ => {
+                    rest.nested.push(nested.clone());
+                    continue;
+                }
+            };
+
+            let param = if path.is_ident("tooltip") {
+                &mut common.tooltip
+            } else if path.is_ident("help") {
+                &mut common.help
+            } else {
+                rest.nested.push(nested.clone());
+                continue;
+            };
+
+            if param.is_some() {
+                return Err(Error::already_defined(path.span()));
+            }
+            *param = Some(hint);
+        }
+
+        Ok((rest, common))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tooltip.is_none()
+            && self.help.is_none()
+            && self.default.is_none()
+            && !self.readonly
+            && self.disabled_if.is_none()
+            && self.enabled_if.is_none()
+            && self.visible_if.is_none()
+            && self.clamp.is_none()
+            && self.validate.is_none()
+            && self.on_change.is_none()
+            && self.on_click.is_none()
+            && self.context_menu.is_none()
+    }
+
+    fn wrap(self, tag: Tag) -> Tag {
+        if self.is_empty() {
+            tag
+        } else {
+            Tag::Common(Box::new(tag), self)
+        }
+    }
+}
+
+impl Tag {
+    /// Removes the `Common` wrapper (if any) from the tag.
+    fn into_common(self) -> (Tag, Common) {
+        match self {
+            Tag::Common(tag, common) => (*tag, common),
+            tag => (tag, Common::default()),
+        }
+    }
+
+    /// Returns `true` for the annotations that render a value of the field, and
+    /// can report input events (buttons not included).
+    fn is_value_widget(&self) -> bool {
+        match self {
+            Tag::Checkbox(Checkbox { map, .. }) => map.is_none(),
+            Tag::Input(Input { map, .. }) => map.is_none(),
+            Tag::Slider(Slider { map, .. }) => map.is_none(),
+            Tag::Drag(Drag { map, .. }) => map.is_none(),
+            Tag::ColorEdit(ColorEdit { map, .. }) => map.is_none(),
+            Tag::ColorPicker(ColorPicker { map, .. }) => map.is_none(),
+            Tag::Common(tag, _) => tag.is_value_widget(),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` for the annotations rendering other widgets after a
+    /// header (`tree`) or as a single item (`child` and `vars`).
+    fn is_container(&self) -> bool {
+        matches!(self, Tag::Tree(_) | Tag::Child(_) | Tag::Vars(_))
+    }
+
+    /// `catch` and `map` params of the annotations that evaluate to `true` when
+    /// the widget modifies the value of the field. `None` for the rest.
+    fn change_params(&self) -> Option<(Option<&Lit>, Option<&Lit>)> {
+        let (catch, map) = match self {
+            Tag::Checkbox(Checkbox { catch, map, .. }) => (catch, map),
+            Tag::Input(Input { catch, map, .. }) => (catch, map),
+            Tag::Slider(Slider { catch, map, .. }) => (catch, map),
+            Tag::Drag(Drag { catch, map, .. }) => (catch, map),
+            Tag::ColorEdit(ColorEdit { catch, map, .. }) => (catch, map),
+            Tag::ColorPicker(ColorPicker { catch, map, .. }) => (catch, map),
+            Tag::MenuItem(MenuItem { catch, map, .. }) => (catch, map),
+            _ => return None,
+        };
+        Some((catch.as_ref(), map.as_ref()))
+    }
+
+    /// Kind and label of the widgets that are recorded by the testing harness
+    /// (`imgui_ext::testing`). Containers are not recorded.
+    fn item(&self, ident: &Ident) -> Option<(&'static str, String)> {
+        let (kind, label) = match self {
+            Tag::Display(Display { label, .. }) => ("display", label),
+            Tag::Checkbox(Checkbox { label, .. }) => ("checkbox", label),
+            Tag::Input(Input { label, .. }) => ("input", label),
+            Tag::Slider(Slider { label, .. }) => ("slider", label),
+            Tag::Drag(Drag { label, .. }) => ("drag", label),
+            Tag::MenuItem(MenuItem { label, .. }) => ("menu_item", label),
+            Tag::ColorButton(ColorButton { label, .. }) => ("color_button", label),
+            Tag::ColorPicker(ColorPicker { label, .. }) => ("color_picker", label),
+            Tag::ColorEdit(ColorEdit { label, .. }) => ("color_edit", label),
+            Tag::Progress(_) => ("progress", &None),
+            Tag::Image(_) => ("image", &None),
+            Tag::ImageButton(_) => ("image_button", &None),
+            Tag::Button(Button {
+                label: Lit::Str(label),
+                ..
+            }) => return Some(("button", label.value())),
+            _ => return None,
+        };
+        match label {
+            Some(Lit::Str(label)) => Some((kind, label.value())),
+            _ => Some((kind, ident.to_string())),
+        }
+    }
+
+    /// Kind and label of the annotations containing other widgets, which are
+    /// recorded by the testing harness as the parents of those widgets.
+    fn container(&self, ident: &Ident) -> Option<(&'static str, String)> {
+        let (kind, label) = match self {
+            Tag::Nested(_) => ("nested", &None),
+            Tag::Menu(Menu { label, .. }) => ("menu", label),
+            Tag::Tree(Tree { label, .. }) => ("tree", label),
+            Tag::Child(Child { label, .. }) => ("child", label),
+            Tag::Popup(Popup {
+                modal: true, label, ..
+            }) => ("modal", label),
+            Tag::Popup(Popup { label, .. }) => ("popup", label),
+            _ => return None,
+        };
+        match label {
+            Some(Lit::Str(label)) => Some((kind, label.value())),
+            _ => Some((kind, ident.to_string())),
+        }
+    }
+
+    /// Params defined in the annotation, by name (the nested widgets of
+    /// containers are not included).
+    fn params(&self) -> Vec<(&'static str, &Lit)> {
+        fn opt<'a>(params: &[(&'static str, Option<&'a Lit>)]) -> Vec<(&'static str, &'a Lit)> {
+            params
+                .iter()
+                .filter_map(|(name, lit)| lit.map(|lit| (*name, lit)))
+                .collect()
+        }
+        match self {
+            Tag::Display(Display { label, display, .. }) => {
+                opt(&[("label", label.as_ref()), ("display", display.as_ref())])
+            }
+            Tag::Checkbox(tag) => tag.params(),
+            Tag::Input(tag) => tag.params(),
+            Tag::Slider(tag) => tag.params(),
+            Tag::Drag(tag) => tag.params(),
+            Tag::Nested(tag) => tag.params(),
+            Tag::Menu(tag) => tag.params(),
+            Tag::MenuItem(tag) => tag.params(),
+            Tag::Progress(tag) => tag.params(),
+            Tag::Image(tag) => tag.params(),
+            Tag::ImageButton(tag) => tag.params(),
+            Tag::Button(tag) => tag.params(),
+            Tag::ColorButton(tag) => tag.params(),
+            Tag::ColorPicker(tag) => tag.params(),
+            Tag::ColorEdit(tag) => tag.params(),
+            Tag::Text(tag) | Tag::TextWrap(tag) => tag.params(),
+            Tag::Bullet(tag) => tag.params(),
+            Tag::Tree(Tree {
+                label, cond, flags, ..
+            }) => opt(&[
+                ("label", label.as_ref()),
+                ("cond", cond.as_ref()),
+                ("flags", flags.as_ref()),
+            ]),
+            Tag::Vars(Vars { style, color, .. }) => {
+                opt(&[("style", style.as_ref()), ("color", color.as_ref())])
+            }
+            Tag::Child(Child {
+                label,
+                size,
+                border,
+                flags,
+                ..
+            }) => opt(&[
+                ("label", label.as_ref()),
+                ("size", size.as_ref()),
+                ("border", border.as_ref()),
+                ("flags", flags.as_ref()),
+            ]),
+            Tag::Popup(Popup {
+                label,
+                open,
+                ok,
+                cancel,
+                catch,
+                ..
+            }) => opt(&[
+                ("label", label.as_ref()),
+                ("open", open.as_ref()),
+                ("ok", ok.as_ref()),
+                ("cancel", cancel.as_ref()),
+                ("catch", catch.as_ref()),
+            ]),
+            Tag::Common(tag, _) => tag.params(),
+            Tag::None | Tag::Separator | Tag::NewLine | Tag::BulletParent => Vec::new(),
+        }
+    }
+
+    /// Show the doc comment of the field when this widget is hovered, unless
+    /// the annotation defines a tooltip already.
+    pub fn with_doc_tooltip(self, span: Span) -> Self {
+        let (tag, mut common) = self.into_common();
+        match tag {
+            // non-interactive annotations, and annotations containing other widgets
+            Tag::None
+            | Tag::Separator
+            | Tag::NewLine
+            | Tag::Text(_)
+            | Tag::TextWrap(_)
+            | Tag::BulletParent
+            | Tag::Bullet(_)
+            | Tag::Nested(_)
+            | Tag::Menu(_)
+            | Tag::Tree(_)
+            | Tag::Vars(_)
+            | Tag::Child(_)
+            | Tag::Popup(_) => common.wrap(tag),
+            tag => {
+                if common.tooltip.is_none() {
+                    common.tooltip = Some(Hint::Doc(span));
+                }
+                common.wrap(tag)
+            }
+        }
+    }
+
+    /// Adds a context menu to the widget, if it renders the value of the field
+    /// (and doesn't define its own menu).
+    pub fn with_context_menu(self, menu: &ContextMenu) -> Self {
+        if !self.is_value_widget() {
+            return self;
+        }
+        let (tag, mut common) = self.into_common();
+        if common.context_menu.is_none() {
+            common.context_menu = Some(menu.clone());
+        }
+        common.wrap(tag)
+    }
+}
+
 pub enum Tag {
     None,
     Display(Display),
@@ -516,6 +1460,8 @@ pub enum Tag {
     Slider(Slider),
     Drag(Drag),
     Nested(Nested),
+    Menu(Menu),
+    MenuItem(MenuItem),
     Progress(Progress),
     Image(Image),
     ImageButton(ImageButton),
@@ -540,6 +1486,11 @@ pub enum Tag {
 
     Tree(Tree),
     Vars(Vars),
+    Child(Child),
+    Popup(Popup),
+
+    /// Any of the above, with some of the params in `Common`.
+    Common(Box<Tag>, Common),
 }
 
 /// meta is the whole (parsed) tag: `#[imgui]` or `#[imgui(...)]`
@@ -554,6 +1505,36 @@ pub fn parse_meta(meta: Meta) -> Result<Vec<Tag>, Error> {
     }
 }
 
+/// Annotations of the fields (for the suggestions of the error messages).
+const ANNOTATIONS: &[&str] = &[
+    "separator",
+    "new_line",
+    "nested",
+    "menu",
+    "menu_item",
+    "display",
+    "checkbox",
+    "input",
+    "drag",
+    "slider",
+    "button",
+    "progress",
+    "image",
+    "image_button",
+    "text",
+    "text_wrap",
+    "tree",
+    "vars",
+    "child",
+    "popup",
+    "modal",
+    "color",
+    "bullet",
+];
+
+/// Modes of the `color(...)` annotation.
+const COLOR_MODES: &[&str] = &["edit", "picker", "button"];
+
 /// Parse the inside of `#[imgui(...)]`
 ///                              ^^^
 /// Possible cases:
@@ -574,11 +1555,10 @@ fn parse_meta_list(meta_list: &MetaList) -> Result<Vec<Tag>, Error> {
 
     for nested in meta_list.nested.iter() {
         match (state, nested) {
-            (_, NestedMeta::Lit(_)) => return Err(Error::invalid_format(meta_list.span())),
+            (_, NestedMeta::Lit(lit)) => return Err(Error::invalid_format(lit.span())),
             // Parse as a label(...)
             (State::Init, NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, .. })))
-                if path_to_ident(&path).to_string() == "label"
-                    || path_to_ident(&path).to_string() == "display" =>
+                if path.is_ident("label") || path.is_ident("display") =>
             {
                 tags.push(Tag::Display(Display::from_meta_list(&meta_list)?));
                 // any errors will have been reported by the previous call to `parse_label`.
@@ -588,12 +1568,14 @@ fn parse_meta_list(meta_list: &MetaList) -> Result<Vec<Tag>, Error> {
 
             // widgets that can take no parameters
             (s, NestedMeta::Meta(Meta::Path(path))) if s == State::Init || s == State::Tags => {
-                let ident = path_to_ident(&path);
+                let ident = path_to_ident(&path)?;
                 match ident.to_string().as_str() {
                     "separator" => tags.push(Tag::Separator),
                     "new_line" => tags.push(Tag::NewLine),
 
                     "nested" => tags.push(Tag::Nested(Default::default())),
+                    "menu" => tags.push(Tag::Menu(Default::default())),
+                    "menu_item" => tags.push(Tag::MenuItem(Default::default())),
                     "display" => tags.push(Tag::Display(Default::default())),
                     "checkbox" => tags.push(Tag::Checkbox(Default::default())),
                     "input" => tags.push(Tag::Input(Default::default())),
@@ -604,34 +1586,45 @@ fn parse_meta_list(meta_list: &MetaList) -> Result<Vec<Tag>, Error> {
                     //"text_wrap" => tags.push(Tag::TextWrap(Default::default())),
                     "tree" => tags.push(Tag::Tree(Default::default())),
                     "vars" => tags.push(Tag::Vars(Default::default())),
+                    "child" => tags.push(Tag::Child(Default::default())),
 
                     // errors
-                    "color" => return Err(Error::invalid_format(meta_list.span())),
-                    "text" => return Err(Error::invalid_format(meta_list.span())),
-                    "text_wrap" => return Err(Error::invalid_format(meta_list.span())),
-                    "slider" => {
-                        Tag::Slider(Slider::from_meta_list(&meta_list)?);
+                    "color" => {
+                        return Err(Error::unexpected_mode(ident.span()).help(
+                            "Expected one of `color(edit)`, `color(picker)` or `color(button)`.",
+                        ))
                     }
-                    "button" => {
-                        Tag::Button(Button::from_meta_list(&meta_list)?);
+                    "text" | "text_wrap" => {
+                        return Err(Error::unexpected_mode(ident.span())
+                            .help(format!("Expected `{}(\"...\")`.", ident)))
                     }
-                    "image" => {
-                        Tag::Image(Image::from_meta_list(&meta_list)?);
+                    "slider" => return Err(Error::missing_param(ident.span(), "min")),
+                    "button" => return Err(Error::missing_param(ident.span(), "label")),
+                    "image" | "image_button" => {
+                        return Err(Error::missing_param(ident.span(), "size"))
                     }
+                    "popup" | "modal" => return Err(Error::missing_param(ident.span(), "open")),
 
-                    _ => return Err(Error::unexpected_mode(meta_list.span())),
+                    name => return Err(Error::unknown_annotation(ident.span(), name, ANNOTATIONS)),
                 }
                 state = State::Tags;
             }
             (s, NestedMeta::Meta(Meta::List(meta_list)))
                 if s == State::Init || s == State::Tags =>
             {
-                let tag = match path_to_ident(&meta_list.path).to_string().as_str() {
+                // params such as `tooltip` are accepted by every annotation
+                let (meta_list, common) = Common::split(meta_list)?;
+                let meta_list = &meta_list;
+
+                let ident = path_to_ident(&meta_list.path)?;
+                let tag = match ident.to_string().as_str() {
                     "separator" => Tag::Separator,
                     "new_line" => Tag::NewLine,
 
                     "display" => Tag::Display(Display::from_meta_list(&meta_list)?),
                     "nested" => Tag::Nested(Nested::from_meta_list(meta_list)?),
+                    "menu" => Tag::Menu(Menu::from_meta_list(meta_list)?),
+                    "menu_item" => Tag::MenuItem(MenuItem::from_meta_list(meta_list)?),
                     "checkbox" => Tag::Checkbox(Checkbox::from_meta_list(meta_list)?),
                     "input" => Tag::Input(Input::from_meta_list(meta_list)?),
                     "drag" => Tag::Drag(Drag::from_meta_list(meta_list)?),
@@ -644,6 +1637,9 @@ fn parse_meta_list(meta_list: &MetaList) -> Result<Vec<Tag>, Error> {
                     "text_wrap" => Tag::TextWrap(Text::from_meta_list2(meta_list)?),
                     "tree" => Tag::Tree(Tree::from_meta_list(meta_list)?),
                     "vars" => Tag::Vars(Vars::from_meta_list(meta_list)?),
+                    "child" => Tag::Child(Child::from_meta_list(meta_list)?),
+                    "popup" => Tag::Popup(Popup::from_meta_list(meta_list, false)?),
+                    "modal" => Tag::Popup(Popup::from_meta_list(meta_list, true)?),
 
                     "color" => {
                         for nested in meta_list.nested.iter() {
@@ -653,14 +1649,19 @@ fn parse_meta_list(meta_list: &MetaList) -> Result<Vec<Tag>, Error> {
                                 //   - `color(picker)`
                                 //   - `color(button)`
                                 NestedMeta::Meta(Meta::Path(path)) => {
-                                    let ident = path_to_ident(&path);
+                                    let ident = path_to_ident(&path)?;
                                     match ident.to_string().as_str() {
                                         "edit" => tags.push(Tag::ColorEdit(Default::default())),
                                         "picker" => tags.push(Tag::ColorPicker(Default::default())),
                                         "button" => tags.push(Tag::ColorButton(Default::default())),
 
-                                        // Compiler error
-                                        _ => return Err(Error::unexpected_mode(ident.span())),
+                                        name => {
+                                            return Err(Error::unknown_annotation(
+                                                ident.span(),
+                                                name,
+                                                COLOR_MODES,
+                                            ))
+                                        }
                                     }
                                 }
 
@@ -669,28 +1670,32 @@ fn parse_meta_list(meta_list: &MetaList) -> Result<Vec<Tag>, Error> {
                                 //   - `color(picker(...))`
                                 //   - `color(button(...))`
                                 NestedMeta::Meta(Meta::List(color_meta_list)) => {
-                                    let ident = path_to_ident(&color_meta_list.path);
+                                    let (color_meta_list, color_common) =
+                                        Common::split(color_meta_list)?;
+                                    let color_meta_list = &color_meta_list;
+                                    let ident = path_to_ident(&color_meta_list.path)?;
                                     match ident.to_string().as_str() {
-                                        "edit" => tags.push(Tag::ColorEdit(
+                                        "edit" => tags.push(color_common.wrap(Tag::ColorEdit(
                                             ColorEdit::from_meta_list(color_meta_list)?,
-                                        )),
-                                        "picker" => tags.push(Tag::ColorPicker(
+                                        ))),
+                                        "picker" => tags.push(color_common.wrap(Tag::ColorPicker(
                                             ColorPicker::from_meta_list(color_meta_list)?,
-                                        )),
-                                        "button" => tags.push(Tag::ColorButton(
+                                        ))),
+                                        "button" => tags.push(color_common.wrap(Tag::ColorButton(
                                             ColorButton::from_meta_list(color_meta_list)?,
-                                        )),
+                                        ))),
 
-                                        // Compiler error
-                                        _ => {
-                                            return Err(Error::unexpected_mode(
-                                                color_meta_list.path.span(),
+                                        name => {
+                                            return Err(Error::unknown_annotation(
+                                                ident.span(),
+                                                name,
+                                                COLOR_MODES,
                                             ))
                                         }
                                     }
                                 }
 
-                                _ => return Err(Error::invalid_format(meta_list.span())),
+                                _ => return Err(Error::invalid_format(nested.span())),
                             }
                         }
 
@@ -718,18 +1723,147 @@ fn parse_meta_list(meta_list: &MetaList) -> Result<Vec<Tag>, Error> {
                             }?
                         }
                     },
-                    _ => return Err(Error::unexpected_mode(meta_list.span())),
+                    name => return Err(Error::unknown_annotation(ident.span(), name, ANNOTATIONS)),
                 };
 
-                tags.push(tag);
+                tags.push(common.wrap(tag));
                 state = State::Tags;
             }
-            _ => panic!(),
+            (_, nested) => return Err(Error::invalid_format(nested.span())),
         }
     }
     Ok(tags)
 }
 
+/// How the widgets render the value of the fields.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Mode {
+    /// `Gui::draw_gui`, where `ext` is `&mut Self`.
+    Edit,
+    /// `Gui::draw_gui_readonly`, where `ext` is `&Self`. The values are never
+    /// modified.
+    ReadOnly,
+}
+
+/// The field an annotation is attached to, passed down to the nested tags.
+#[derive(Clone, Copy)]
+pub struct Context<'a> {
+    pub ident: &'a Ident,
+    pub ty: &'a Type,
+    pub attr: &'a Attribute,
+    /// The `///` comments of the field.
+    pub doc: Option<&'a str>,
+    pub mode: Mode,
+    /// Tooltip and `(?)` marker of a container annotation (`tree`, `child` or
+    /// `vars`), rendered after its header rather than after its contents.
+    pub header: Option<&'a TokenStream>,
+}
+
+impl Context<'_> {
+    /// Context of the widgets inside of a container annotation.
+    fn contents(self) -> Self {
+        Context {
+            header: None,
+            ..self
+        }
+    }
+}
+
+/// Condition of a `visible_if` or `enabled_if` param. A single identifier
+/// names a `bool` field of the struct, any other path a `fn(&Self) -> bool`.
+fn condition(lit: &Lit) -> Result<TokenStream, Error> {
+    match lit {
+        Lit::Str(path_str) => {
+            let path: syn::Path = syn::parse_str(&path_str.value())
+                .map_err(|_| Error::invalid_path(path_str.span(), &path_str.value()))?;
+            match path.get_ident() {
+                Some(field) => {
+                    let mut field = field.clone();
+                    field.set_span(path_str.span());
+                    Ok(quote!(ext.#field))
+                }
+                None => Ok(quote!((#path(ext)))),
+            }
+        }
+        lit => Err(Error::invalid_format(lit.span())),
+    }
+}
+
+/// Call to the method of an `on_change` or `on_click` param. A single
+/// identifier names a method, any other path a function taking `&mut Self`
+/// (and optionally the event, returned by `event`).
+fn callback(lit: &Lit, event: TokenStream) -> Result<TokenStream, Error> {
+    match lit {
+        Lit::Str(path_str) => {
+            let path: syn::Path = syn::parse_str(&path_str.value())
+                .map_err(|_| Error::invalid_path(path_str.span(), &path_str.value()))?;
+            match path.get_ident() {
+                Some(method) => {
+                    let mut method = method.clone();
+                    method.set_span(path_str.span());
+                    Ok(quote!(ext.#method();))
+                }
+                None => Ok(quote! {
+                    imgui_ext::callback::Callback::call(#path, ext, #event);
+                }),
+            }
+        }
+        lit => Err(Error::invalid_format(lit.span())),
+    }
+}
+
+/// Parses a numeric param, which can also be written as a string.
+fn number(lit: &Lit) -> Result<TokenStream, Error> {
+    match lit {
+        Lit::Int(_) | Lit::Float(_) => Ok(quote!(#lit)),
+        Lit::Str(value) => {
+            let int = value.value().parse().map(Literal::i64_unsuffixed);
+            let float = value.value().parse().map(Literal::f64_unsuffixed);
+            match (int, float) {
+                (Ok(value), _) => Ok(quote!(#value)),
+                (Err(_), Ok(value)) => Ok(quote!(#value)),
+                _ => Err(Error::expected(value.span(), "a number")),
+            }
+        }
+        lit => Err(Error::expected(lit.span(), "a number")),
+    }
+}
+
+/// Parses the `map` param of an annotation.
+fn map_path(map: Option<&Lit>) -> Result<Option<syn::Path>, Error> {
+    match map {
+        Some(Lit::Str(map)) => syn::parse_str(&map.value())
+            .map(Some)
+            .map_err(|_| Error::invalid_path(map.span(), &map.value())),
+        Some(lit) => Err(Error::invalid_format(lit.span())),
+        None => Ok(None),
+    }
+}
+
+/// Parses an identifier written as a string (`"method"`, `"Variant"`, ...).
+pub fn lit_ident(lit: &LitStr) -> Result<Ident, Error> {
+    let mut ident: Ident =
+        syn::parse_str(&lit.value()).map_err(|_| Error::expected(lit.span(), "an identifier"))?;
+    ident.set_span(lit.span());
+    Ok(ident)
+}
+
+/// Parses the path to a function written as a string.
+fn lit_path(lit: &LitStr) -> Result<syn::Path, Error> {
+    syn::parse_str(&lit.value()).map_err(|_| Error::invalid_path(lit.span(), &lit.value()))
+}
+
+/// Tokens of the field passed to a widget. The map function takes `&mut`, so
+/// mapped fields are cloned in read-only mode.
+fn elem_tokens(ident: &Ident, map: Option<&syn::Path>, mode: Mode) -> TokenStream {
+    match (mode, map) {
+        (Mode::Edit, None) => quote!(&mut ext.#ident),
+        (Mode::Edit, Some(map)) => quote!(#map(&mut ext.#ident)),
+        (Mode::ReadOnly, None) => quote!(&ext.#ident),
+        (Mode::ReadOnly, Some(map)) => quote!(&*#map(&mut ext.#ident.clone())),
+    }
+}
+
 /// Output source code for a given field, a given attribute, and one of the
 /// parsed `Tag`s
 ///
@@ -738,16 +1872,318 @@ fn parse_meta_list(meta_list: &MetaList) -> Result<Vec<Tag>, Error> {
 ///
 /// This function needs to be called twice (once per Tag)
 pub fn emmit_tag_tokens(
-    ident: &Ident,
-    _ty: &Type,
-    attr: &Attribute,
+    ctx: Context,
     tag: &Tag,
     fields: &mut TokenStream,
     methods: &mut TokenStream,
     input_fields: &mut HashSet<String>,
 ) -> Result<TokenStream, Error> {
+    let Context {
+        ident,
+        ty: _ty,
+        attr,
+        doc,
+        mode,
+        header,
+    } = ctx;
     let tokens = match tag {
         Tag::None => quote!(),
+        Tag::Common(
+            tag,
+            Common {
+                tooltip,
+                help,
+                default,
+                readonly,
+                disabled_if,
+                enabled_if,
+                visible_if,
+                clamp,
+                validate,
+                on_change,
+                on_click,
+                context_menu,
+            },
+        ) => {
+            let mode = if *readonly { Mode::ReadOnly } else { mode };
+            let disabled_if = match disabled_if {
+                Some(Lit::Str(method)) => {
+                    let method = lit_ident(method)?;
+                    Some(quote!(ext.#method()))
+                }
+                Some(lit) => return Err(Error::invalid_format(lit.span())),
+                None => None,
+            };
+            let disabled = match (disabled_if, enabled_if) {
+                (Some(disabled), Some(enabled)) => {
+                    let enabled = condition(enabled)?;
+                    Some(quote!(#disabled || !#enabled))
+                }
+                (None, Some(enabled)) => {
+                    let enabled = condition(enabled)?;
+                    Some(quote!(!#enabled))
+                }
+                (disabled, None) => disabled,
+            };
+            let mut hint_tokens = TokenStream::new();
+
+            if let Some(tooltip) = tooltip {
+                let text = tooltip.text(doc)?;
+                hint_tokens.extend(quote! {
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text(#text);
+                    }
+                });
+            }
+
+            if let Some(help) = help {
+                let text = help.text(doc)?;
+                hint_tokens.extend(quote! {
+                    ui.same_line(0.0);
+                    ui.text_disabled("(?)");
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text(#text);
+                    }
+                });
+            }
+
+            // the contents of a container are rendered after its header
+            let header = Some(&hint_tokens).filter(|_| tag.is_container());
+            let tokens = match (mode, &disabled) {
+                (Mode::Edit, Some(disabled)) => {
+                    let edit = emmit_tag_tokens(
+                        Context {
+                            mode: Mode::Edit,
+                            header,
+                            ..ctx
+                        },
+                        tag,
+                        fields,
+                        methods,
+                        input_fields,
+                    )?;
+                    // the events have been declared already
+                    let readonly = emmit_tag_tokens(
+                        Context {
+                            mode: Mode::ReadOnly,
+                            header,
+                            ..ctx
+                        },
+                        tag,
+                        &mut TokenStream::new(),
+                        &mut TokenStream::new(),
+                        &mut HashSet::new(),
+                    )?;
+                    quote! {
+                        if #disabled {
+                            #readonly;
+                            Default::default()
+                        } else {
+                            #edit
+                        }
+                    }
+                }
+                _ => emmit_tag_tokens(
+                    Context {
+                        mode,
+                        header,
+                        ..ctx
+                    },
+                    tag,
+                    fields,
+                    methods,
+                    input_fields,
+                )?,
+            };
+
+            // hooks applied after the widget modifies the value
+            let mut prev_tokens = TokenStream::new();
+            let mut change_tokens = TokenStream::new();
+            let mut valid_tokens = TokenStream::new();
+            let hooks = [
+                clamp.as_ref().map(|(min, _)| min),
+                validate.as_ref(),
+                on_change.as_ref(),
+            ];
+            let (catch, map) = match (hooks.iter().flatten().next(), tag.change_params()) {
+                (Some(lit), None) => return Err(Error::invalid_format(lit.span())),
+                (_, Some((catch, map))) => (catch, map),
+                (None, None) => (None, None),
+            };
+            if let Some(lit) = on_click {
+                match **tag {
+                    Tag::Button(_) => {}
+                    _ => return Err(Error::invalid_format(lit.span())),
+                }
+            }
+            if mode == Mode::Edit {
+                if let Some((min, max)) = clamp {
+                    // the bounds are cast to the type of the elements
+                    let (min, max) = (number(min)?, number(max)?);
+                    let elem = elem_tokens(ident, map_path(map)?.as_ref(), Mode::Edit);
+                    change_tokens.extend(quote! {
+                        if _ev {
+                            imgui_ext::validate::Clamp::clamp(#elem, (#min) as _, (#max) as _);
+                        }
+                    });
+                }
+                let changed = match validate {
+                    Some(Lit::Str(path)) => {
+                        let path: syn::Path = syn::parse_str(&path.value())
+                            .map_err(|_| Error::invalid_path(path.span(), &path.value()))?;
+                        // invalid changes are kept, but not reported
+                        let event = match catch {
+                            Some(Lit::Str(catch)) => lit_ident(catch)?,
+                            Some(lit) => {
+                                return Err(Error::expected(lit.span(), "a string literal"))
+                            }
+                            None => ident.clone(),
+                        };
+                        let id = Literal::string(&format!("##validate_{}", ident.unraw()));
+                        prev_tokens.extend(quote! {
+                            let _prev = events.#event;
+                        });
+                        valid_tokens.extend(quote! {
+                            let _valid = imgui_ext::validate::__validate(
+                                ui,
+                                imgui::im_str!(#id),
+                                _ev,
+                                || #path(&ext.#ident),
+                            );
+                            if !_valid {
+                                events.#event = _prev;
+                            }
+                        });
+                        quote!(_ev && _valid)
+                    }
+                    Some(lit) => return Err(Error::invalid_format(lit.span())),
+                    None => quote!(_ev),
+                };
+                if let Some(on_change) = on_change {
+                    // the event of a change is the new value
+                    let call = callback(on_change, quote!(|ext: &Self| ext.#ident.clone()))?;
+                    valid_tokens.extend(quote! {
+                        if #changed {
+                            #call
+                        }
+                    });
+                }
+                if let Some(on_click) = on_click {
+                    let call = callback(on_click, quote!(|_: &Self| ()))?;
+                    change_tokens.extend(quote! {
+                        if _ev {
+                            #call
+                        }
+                    });
+                }
+            }
+
+            let mut common_tokens = TokenStream::new();
+            if !tag.is_container() {
+                common_tokens.extend(hint_tokens.clone());
+            }
+
+            if let Some(default) = default {
+                if !matches!(context_menu, Some(ContextMenu { reset: true, .. })) {
+                    return Err(Error::unused_default(default.span()));
+                }
+            }
+
+            // the items of the context menu modify the value
+            let context_menu = context_menu.as_ref().filter(|_| mode == Mode::Edit);
+            if let Some(ContextMenu {
+                reset,
+                copy,
+                paste,
+                items,
+            }) = context_menu
+            {
+                let mut menu_tokens = TokenStream::new();
+
+                if *reset {
+                    let value = match default {
+                        Some(Lit::Str(path)) => {
+                            let fn_ident: syn::Path = syn::parse_str(&path.value())
+                                .map_err(|_| Error::invalid_path(path.span(), &path.value()))?;
+                            quote!(#fn_ident().into())
+                        }
+                        Some(lit) => quote!(#lit),
+                        None => quote!(<Self as Default>::default().#ident),
+                    };
+                    menu_tokens.extend(quote! {
+                        if context_menu::item(ui, im_str!("Reset to default")) {
+                            ext.#ident = #value;
+                        }
+                    });
+                }
+                if *copy {
+                    menu_tokens.extend(quote! {
+                        if context_menu::item(ui, im_str!("Copy value")) {
+                            context_menu::set_clipboard(ui, &ext.#ident);
+                        }
+                    });
+                }
+                if *paste {
+                    menu_tokens.extend(quote! {
+                        if context_menu::item(ui, im_str!("Paste value")) {
+                            if let Some(value) = context_menu::clipboard(ui) {
+                                ext.#ident = value;
+                            }
+                        }
+                    });
+                }
+                if (*reset || *copy || *paste) && !items.is_empty() {
+                    menu_tokens.extend(quote!(ui.separator();));
+                }
+                for (label, call) in items.iter() {
+                    let (label, call) = match (label, call) {
+                        (Lit::Str(label), Lit::Str(call)) => {
+                            let call: syn::Path = syn::parse_str(&call.value())
+                                .map_err(|_| Error::invalid_path(call.span(), &call.value()))?;
+                            (label, call)
+                        }
+                        (Lit::Str(_), call) => return Err(Error::invalid_format(call.span())),
+                        (label, _) => return Err(Error::invalid_format(label.span())),
+                    };
+                    menu_tokens.extend(quote! {
+                        if context_menu::item(ui, im_str!(#label)) {
+                            #call(ext);
+                        }
+                    });
+                }
+
+                let id = Literal::string(&format!("##context_menu_{}", ident));
+                let menu_tokens = quote! {{
+                    use imgui_ext::context_menu;
+                    use imgui::im_str;
+                    context_menu::build(ui, im_str!(#id), || { #menu_tokens });
+                }};
+                common_tokens.extend(match disabled {
+                    Some(disabled) => quote!(if !(#disabled) #menu_tokens),
+                    None => menu_tokens,
+                });
+            }
+
+            let tokens = quote! {
+                #prev_tokens
+                let _ev = { #tokens };
+                #change_tokens
+                #common_tokens
+                #valid_tokens
+            };
+
+            match visible_if {
+                Some(visible) => {
+                    let visible = condition(visible)?;
+                    quote! {
+                        if #visible {
+                            #tokens
+                        }
+                    }
+                }
+                None => quote!({ #tokens }),
+            }
+        }
         Tag::Separator => quote!({ ui.separator() }),
         Tag::NewLine => quote!({ ui.new_line() }),
         Tag::Vars(Vars {
@@ -758,21 +2194,15 @@ pub fn emmit_tag_tokens(
             let mut tokens = TokenStream::new();
             if let Some(tags) = content.as_ref() {
                 for tag in tags.iter() {
-                    tokens.extend(emmit_tag_tokens(
-                        ident,
-                        _ty,
-                        attr,
-                        tag,
-                        fields,
-                        methods,
-                        input_fields,
-                    )?);
+                    let tag_tokens =
+                        emmit_tag_tokens(ctx.contents(), tag, fields, methods, input_fields)?;
+                    tokens.extend(quote!(#tag_tokens;));
                 }
             }
 
             let tokens = match color {
                 Some(Lit::Str(color)) => {
-                    let ident = Ident::new(&color.value(), color.span());
+                    let ident = lit_path(color)?;
                     quote! {
                         {
                             let _color = ui.push_style_colors(#ident().into_iter() );
@@ -781,22 +2211,30 @@ pub fn emmit_tag_tokens(
                     }
                 }
                 None => tokens,
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             };
 
             let tokens = match style {
                 Some(Lit::Str(style)) => {
-                    let ident = Ident::new(&style.value(), style.span());
+                    let ident = lit_path(style)?;
                     quote! {{
                         let _style = ui.push_style_vars(#ident().into_iter() );
                         #tokens
                     }}
                 }
                 None => tokens,
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             };
 
-            quote!( { #tokens } )
+            match header {
+                // the group is a single item, hovered along with any of the
+                // widgets inside of it
+                Some(header) => quote! {{
+                    ui.group(|| { #tokens });
+                    #header
+                }},
+                None => quote!( { #tokens } ),
+            }
         }
         Tag::Tree(Tree {
             label,
@@ -807,52 +2245,218 @@ pub fn emmit_tag_tokens(
             let label = match label {
                 Some(Lit::Str(s)) => s.value(),
                 None => ident.to_string(),
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
+            };
+            let label = Literal::string(&label);
+
+            // node contents
+            let mut node_tokens = TokenStream::new();
+            if let Some(tags) = node.as_ref() {
+                for tag in tags.iter() {
+                    let tag_tokens =
+                        emmit_tag_tokens(ctx.contents(), tag, fields, methods, input_fields)?;
+                    node_tokens.extend(quote!(#tag_tokens;));
+                }
+            }
+
+            let mut tree_tokens = TokenStream::default();
+
+            let flags_fn = match flags {
+                Some(Lit::Str(flags)) => {
+                    let fn_ident: syn::Path = syn::parse_str(&flags.value())
+                        .map_err(|_| Error::invalid_path(flags.span(), &flags.value()))?;
+                    tree_tokens.extend(quote! {tree = tree.flags(#fn_ident());});
+                    Some(fn_ident)
+                }
+                None => None,
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
+            };
+
+            match cond {
+                Some(Lit::Str(cond)) => {
+                    let ident = lit_ident(cond)?;
+                    tree_tokens
+                        .extend(quote! {tree = tree.opened(true, imgui::Condition::#ident);});
+                }
+                None => {}
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
+            }
+
+            let header_tokens = header.cloned().unwrap_or_default();
+
+            // open state of the node before it is toggled for the first time
+            let default_open = match (cond, flags_fn) {
+                (Some(_), _) => quote!(true),
+                (None, Some(flags)) => {
+                    quote!(#flags().contains(imgui::ImGuiTreeNodeFlags::DefaultOpen))
+                }
+                (None, None) => quote!(false),
+            };
+
+            quote! {{
+                let mut _closed = true;
+                let mut tree = imgui::TreeNode::new(ui, imgui::im_str!(#label));
+                { #tree_tokens }
+                // the fields inside the node match the filter. The node is
+                // opened for this frame only (the state is restored on drop)
+                let _state = imgui_ext::__tree_state(imgui::im_str!(#label), #default_open);
+                if imgui_ext::__filtering() {
+                    tree = tree.opened(true, imgui::Condition::Always);
+                }
+                tree.build(|| { #header_tokens; _closed = false; #node_tokens });
+                // the node is the last item when it is closed
+                if _closed { #header_tokens }
+            }}
+        }
+        Tag::Child(Child {
+            label,
+            size,
+            border,
+            flags,
+            content,
+        }) => {
+            let label = match label {
+                Some(Lit::Str(s)) => s.value(),
+                None => ident.to_string(),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
+            };
+            let label = Literal::string(&label);
+
+            // child window contents
+            let mut content_tokens = TokenStream::new();
+            if let Some(tags) = content.as_ref() {
+                for tag in tags.iter() {
+                    let tag_tokens =
+                        emmit_tag_tokens(ctx.contents(), tag, fields, methods, input_fields)?;
+                    content_tokens.extend(quote!(#tag_tokens;));
+                }
+            }
+
+            let mut child_tokens = TokenStream::new();
+
+            match size {
+                Some(Lit::Str(size)) => {
+                    let fn_ident: syn::Path = syn::parse_str(&size.value())
+                        .map_err(|_| Error::invalid_path(size.span(), &size.value()))?;
+                    child_tokens.extend(quote! { child = child.size(#fn_ident().into()); });
+                }
+                None => {}
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
+            }
+
+            match border {
+                Some(Lit::Bool(border)) => {
+                    child_tokens.extend(quote! { child = child.border(#border); });
+                }
+                None => {}
+                Some(lit) => return Err(Error::expected(lit.span(), "a boolean literal")),
+            }
+
+            match flags {
+                Some(Lit::Str(flags)) => {
+                    let fn_ident: syn::Path = syn::parse_str(&flags.value())
+                        .map_err(|_| Error::invalid_path(flags.span(), &flags.value()))?;
+                    child_tokens.extend(quote! { child = child.flags(#fn_ident()); });
+                }
+                None => {}
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
+            }
+
+            quote! {{
+                let mut child = imgui::ChildWindow::new(imgui::im_str!(#label));
+                { #child_tokens }
+                child.build(ui, || { #content_tokens });
+                // the child window is the last item after it's built
+                #header
+            }}
+        }
+        Tag::Popup(Popup {
+            modal,
+            label,
+            open,
+            ok,
+            cancel,
+            catch,
+            content,
+        }) => {
+            let label = match label {
+                Some(Lit::Str(s)) => s.value(),
+                None => ident.to_string(),
+                Some(lit) => return Err(Error::invalid_format(lit.span())),
             };
             let label = Literal::string(&label);
 
-            // node contents
-            let mut node_tokens = TokenStream::new();
-            if let Some(tags) = node.as_ref() {
+            let open = match open {
+                Some(Lit::Str(open)) => lit_ident(open)?,
+                Some(lit) => return Err(Error::invalid_format(lit.span())),
+                None => return Err(Error::missing_param(attr.span(), "open")),
+            };
+
+            // popup contents
+            let mut content_tokens = TokenStream::new();
+            if let Some(tags) = content.as_ref() {
                 for tag in tags.iter() {
-                    node_tokens.extend(emmit_tag_tokens(
-                        ident,
-                        _ty,
-                        attr,
-                        tag,
-                        fields,
-                        methods,
-                        input_fields,
-                    )?);
+                    let tag_tokens =
+                        emmit_tag_tokens(ctx.contents(), tag, fields, methods, input_fields)?;
+                    content_tokens.extend(quote!(#tag_tokens;));
                 }
             }
 
-            let mut tree_tokens = TokenStream::default();
-
-            match flags {
-                Some(Lit::Str(flags)) => {
-                    let fn_ident: syn::Path =
-                        syn::parse_str(&flags.value()).expect("Error parsing function path.");
-                    tree_tokens.extend(quote! {tree = tree.flags(#fn_ident());});
+            // A modal with no buttons couldn't be closed.
+            let (ok, cancel) = match (ok, cancel) {
+                (None, None) if *modal => (Some("OK".to_string()), Some("Cancel".to_string())),
+                (ok, cancel) => {
+                    let text = |lit: &Option<Lit>| match lit {
+                        Some(Lit::Str(s)) => Ok(Some(s.value())),
+                        Some(lit) => Err(Error::invalid_format(lit.span())),
+                        None => Ok(None),
+                    };
+                    (text(ok)?, text(cancel)?)
                 }
-                None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
-            }
+            };
 
-            match cond {
-                Some(Lit::Str(cond)) => {
-                    let ident = Ident::new(&cond.value(), flags.span());
-                    tree_tokens
-                        .extend(quote! {tree = tree.opened(true, imgui::Condition::#ident);});
+            let catch = match catch {
+                Some(Lit::Str(catch)) => lit_ident(catch)?.unraw(),
+                Some(lit) => return Err(Error::invalid_format(lit.span())),
+                None => ident.unraw(),
+            };
+
+            let mut buttons = TokenStream::new();
+            for (button, suffix) in [(ok, "ok"), (cancel, "cancel")].iter() {
+                if let Some(button) = button {
+                    if !buttons.is_empty() {
+                        buttons.extend(quote!(ui.same_line(0.0);));
+                    }
+                    let button = Literal::string(button);
+                    let event = Ident::new(&format!("{}_{}", catch, suffix), ident.span());
+                    if input_fields.insert(event.to_string()) {
+                        fields.extend(quote! { pub #event: bool , });
+                        methods.extend(quote! { pub fn #event(&self) -> bool { self.#event } });
+                    }
+                    buttons.extend(quote! {
+                        if ui.button(im_str!(#button), [120.0, 0.0]) {
+                            events.#event = true;
+                            ui.close_current_popup();
+                        }
+                    });
                 }
-                None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
             }
+            if !buttons.is_empty() {
+                buttons = quote!(ui.separator(); #buttons);
+            }
+
+            let popup = if *modal {
+                quote!(ui.popup_modal(im_str!(#label)).build(|| { #content_tokens #buttons });)
+            } else {
+                quote!(ui.popup(im_str!(#label), || { #content_tokens #buttons });)
+            };
 
             quote! {{
-                let mut tree = imgui::TreeNode::new(ui, imgui::im_str!(#label));
-                { #tree_tokens }
-                tree.build(|| { #node_tokens })
+                use imgui::im_str;
+                if events.#open {
+                    ui.open_popup(im_str!(#label));
+                }
+                #popup
             }}
         }
         Tag::ImageButton(ImageButton {
@@ -864,8 +2468,8 @@ pub fn emmit_tag_tokens(
             tint,
         }) => {
             let size = match size {
-                Lit::Str(size) => Ident::new(&size.value(), size.span()),
-                _ => return Err(Error::invalid_format(attr.span())),
+                Lit::Str(size) => lit_path(size)?,
+                lit => return Err(Error::expected(lit.span(), "a string literal")),
             };
 
             let mut params = quote! {
@@ -886,55 +2490,61 @@ pub fn emmit_tag_tokens(
                         .value()
                         .parse()
                         .map(Literal::i32_unsuffixed)
-                        .expect("frame_padding expected to be numeric (i32).");
+                        .map_err(|_| Error::expected(value_str.span(), "an integer"))?;
                     params.extend(quote!(params.frame_padding = Some(#value);));
                 }
                 Some(Lit::Int(value)) => {
                     params.extend(quote!(params.frame_padding = Some(#value);));
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "an integer")),
             }
             match uv0 {
                 Some(Lit::Str(uv0)) => {
-                    let fn_ident: syn::Path =
-                        syn::parse_str(&uv0.value()).expect("Error parsing function path.");
+                    let fn_ident: syn::Path = syn::parse_str(&uv0.value())
+                        .map_err(|_| Error::invalid_path(uv0.span(), &uv0.value()))?;
                     params.extend(quote! {{ params.uv0 = Some( #fn_ident().into() ); }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
             match uv1 {
                 Some(Lit::Str(uv1)) => {
-                    let fn_ident: syn::Path =
-                        syn::parse_str(&uv1.value()).expect("Error parsing function path.");
+                    let fn_ident: syn::Path = syn::parse_str(&uv1.value())
+                        .map_err(|_| Error::invalid_path(uv1.span(), &uv1.value()))?;
                     params.extend(quote! {{ params.uv1 = Some( #fn_ident().into() ); }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
             match tint {
                 Some(Lit::Str(size)) => {
-                    let fn_ident: syn::Path =
-                        syn::parse_str(&size.value()).expect("Error parsing function path.");
+                    let fn_ident: syn::Path = syn::parse_str(&size.value())
+                        .map_err(|_| Error::invalid_path(size.span(), &size.value()))?;
                     params.extend(quote! {{ params.tint = Some( #fn_ident().into() ); }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
             match background {
                 Some(Lit::Str(size)) => {
-                    let fn_ident: syn::Path =
-                        syn::parse_str(&size.value()).expect("Error parsing function path.");
+                    let fn_ident: syn::Path = syn::parse_str(&size.value())
+                        .map_err(|_| Error::invalid_path(size.span(), &size.value()))?;
                     params.extend(quote! {{ params.background = Some( #fn_ident().into() ); }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
+            }
+            match mode {
+                Mode::Edit => quote! {{
+                    use imgui_ext::image_button::ImageButton;
+                    ImageButton::build(ui, ext.#ident, { #params ; params });
+                }},
+                Mode::ReadOnly => quote! {{
+                    use imgui_ext::image_button::ImageButton;
+                    ImageButton::build_readonly(ui, ext.#ident, { #params ; params });
+                }},
             }
-            quote! {{
-                use imgui_ext::image::Image;
-                Image::build(ui, ext.#ident, { #params ; params });
-            }}
         }
         Tag::Image(Image {
             size,
@@ -944,8 +2554,8 @@ pub fn emmit_tag_tokens(
             uv1,
         }) => {
             let size = match size {
-                Lit::Str(size) => Ident::new(&size.value(), size.span()),
-                _ => return Err(Error::invalid_format(attr.span())),
+                Lit::Str(size) => lit_path(size)?,
+                lit => return Err(Error::expected(lit.span(), "a string literal")),
             };
 
             let mut params = quote! {
@@ -961,44 +2571,50 @@ pub fn emmit_tag_tokens(
             };
             match uv0 {
                 Some(Lit::Str(uv0)) => {
-                    let fn_ident: syn::Path =
-                        syn::parse_str(&uv0.value()).expect("Error parsing function path.");
+                    let fn_ident: syn::Path = syn::parse_str(&uv0.value())
+                        .map_err(|_| Error::invalid_path(uv0.span(), &uv0.value()))?;
                     params.extend(quote! {{ params.uv0 = Some( #fn_ident().into() ); }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
             match uv1 {
                 Some(Lit::Str(uv1)) => {
-                    let fn_ident: syn::Path =
-                        syn::parse_str(&uv1.value()).expect("Error parsing function path.");
+                    let fn_ident: syn::Path = syn::parse_str(&uv1.value())
+                        .map_err(|_| Error::invalid_path(uv1.span(), &uv1.value()))?;
                     params.extend(quote! {{ params.uv1 = Some( #fn_ident().into() ); }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
             match tint {
                 Some(Lit::Str(size)) => {
-                    let fn_ident: syn::Path =
-                        syn::parse_str(&size.value()).expect("Error parsing function path.");
+                    let fn_ident: syn::Path = syn::parse_str(&size.value())
+                        .map_err(|_| Error::invalid_path(size.span(), &size.value()))?;
                     params.extend(quote! {{ params.tint = Some( #fn_ident().into() ); }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
             match border {
                 Some(Lit::Str(size)) => {
-                    let fn_ident: syn::Path =
-                        syn::parse_str(&size.value()).expect("Error parsing function path.");
+                    let fn_ident: syn::Path = syn::parse_str(&size.value())
+                        .map_err(|_| Error::invalid_path(size.span(), &size.value()))?;
                     params.extend(quote! {{ params.border = Some( #fn_ident().into() ); }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
+            }
+            match mode {
+                Mode::Edit => quote! {{
+                    use imgui_ext::image::Image;
+                    Image::build(ui, ext.#ident, { #params ; params });
+                }},
+                Mode::ReadOnly => quote! {{
+                    use imgui_ext::image::Image;
+                    Image::build_readonly(ui, ext.#ident, { #params ; params });
+                }},
             }
-            quote! {{
-                use imgui_ext::image::Image;
-                Image::build(ui, ext.#ident, { #params ; params });
-            }}
         }
         Tag::Progress(Progress { overlay, size }) => {
             let mut params = quote! {
@@ -1020,23 +2636,29 @@ pub fn emmit_tag_tokens(
                     let overlay = Literal::string(&ident_str);
                     params.extend(quote! {{ params.overlay = Some(im_str!(#overlay)); }});
                 }
-                _ => return Err(Error::invalid_format(attr.span())),
+                (Some(lit), _) => return Err(Error::expected(lit.span(), "a string literal")),
             }
 
             match size {
                 Some(Lit::Str(size)) => {
-                    let fn_ident: syn::Path =
-                        syn::parse_str(&size.value()).expect("Error parsing function path.");
+                    let fn_ident: syn::Path = syn::parse_str(&size.value())
+                        .map_err(|_| Error::invalid_path(size.span(), &size.value()))?;
                     params.extend(quote! {{ params.size = Some( #fn_ident().into() ); }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
 
-            quote! {{
-                use imgui_ext::progress::Progress;
-                Progress::build(ui, &ext.#ident, { #params; params });
-            }}
+            match mode {
+                Mode::Edit => quote! {{
+                    use imgui_ext::progress::Progress;
+                    Progress::build(ui, &ext.#ident, { #params; params });
+                }},
+                Mode::ReadOnly => quote! {{
+                    use imgui_ext::progress::Progress;
+                    Progress::build_readonly(ui, &ext.#ident, { #params; params });
+                }},
+            }
         }
         Tag::Text(Text { lit }) => {
             match lit {
@@ -1044,7 +2666,7 @@ pub fn emmit_tag_tokens(
                 Lit::Str(lit) => quote! { ui.text(#lit); },
 
                 // Invalid format. Raise compiler error.
-                _ => return Err(Error::invalid_format(attr.span())),
+                lit => return Err(Error::expected(lit.span(), "a string literal")),
             }
         }
         Tag::TextWrap(Text { lit }) => {
@@ -1052,7 +2674,7 @@ pub fn emmit_tag_tokens(
                 Lit::Str(lit) => quote! { ui.text_wrapped(imgui::im_str!(#lit)); },
 
                 // Invalid format. Raise compiler error.
-                _ => return Err(Error::invalid_format(attr.span())),
+                lit => return Err(Error::expected(lit.span(), "a string literal")),
             }
         }
         Tag::ColorEdit(ColorEdit {
@@ -1069,7 +2691,7 @@ pub fn emmit_tag_tokens(
                 Some(Lit::Str(stri)) => stri.value(),
                 None => ident.to_string(),
                 // TODO proper error span
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             };
             let label = Literal::string(&label);
             let mut params = quote! {
@@ -1087,86 +2709,88 @@ pub fn emmit_tag_tokens(
 
             match flags {
                 Some(Lit::Str(flags)) => {
-                    let ident = Ident::new(&flags.value(), flags.span());
+                    let ident = lit_path(flags)?;
                     params.extend(quote! { params.flags = Some( #ident() ); });
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
 
             match preview {
                 Some(Lit::Str(c)) => {
-                    let var = Ident::new(&c.value(), ident.span());
+                    let var = lit_ident(c)?;
                     params.extend(quote! {{
                         params.preview = Some( imgui::ColorPreview::#var );
                     }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
 
             match input_mode {
                 Some(Lit::Str(c)) => {
-                    let var = Ident::new(&c.value(), ident.span());
+                    let var = lit_ident(c)?;
                     params.extend(quote! {{
                         params.input_mode = Some( imgui::ColorEditInputMode::#var );
                     }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
 
             match display_mode {
                 Some(Lit::Str(c)) => {
-                    let var = Ident::new(&c.value(), ident.span());
+                    let var = lit_ident(c)?;
                     params.extend(quote! {{
                         params.display_mode = Some( imgui::ColorEditDisplayMode::#var );
                     }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
 
             match format {
                 Some(Lit::Str(c)) => {
-                    let var = Ident::new(&c.value(), ident.span());
+                    let var = lit_ident(c)?;
                     params.extend(quote! {{
                         params.format = Some( imgui::ColorFormat::#var );
                     }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
 
-            let catch_ident =
-                catch_ident(attr, ident, catch.as_ref(), input_fields, fields, methods)?;
+            let catch_ident = catch_ident(ident, catch.as_ref(), input_fields, fields, methods)?;
 
-            match map {
-                None => {
+            let map = map_path(map.as_ref())?;
+            match mode {
+                Mode::Edit => {
+                    let elem = elem_tokens(ident, map.as_ref(), mode);
                     quote! {{
                         use imgui_ext::color::ColorEdit;
-                        let _ev = ColorEdit::build(ui, &mut ext.#ident, { #params ; params });
+                        let _ev = ColorEdit::build(ui, #elem, { #params ; params });
                         events.#catch_ident |= _ev;
+                        _ev
                     }}
                 }
-                Some(Lit::Str(map)) => {
-                    // TODO error handling
-                    let map_path: syn::Path =
-                        syn::parse_str(&map.value()).expect("Error parsing parth to function.");
+                Mode::ReadOnly => {
+                    // the color widgets take `&mut` to the value
+                    let elem = match map {
+                        Some(map) => quote!(#map(&mut ext.#ident.clone())),
+                        None => quote!(&mut ext.#ident.clone()),
+                    };
                     quote! {{
                         use imgui_ext::color::ColorEdit;
-                        let _ev = ColorEdit::build(ui, #map_path(&mut ext.#ident), { #params ; params });
-                        events.#catch_ident |= _ev;
+                        ColorEdit::build_readonly(ui, #elem, { #params ; params });
                     }}
                 }
-                _ => return Err(Error::invalid_format(attr.span())),
             }
         }
         Tag::ColorPicker(ColorPicker {
             label,
             flags,
             preview,
-            mode,
+            mode: picker_mode,
             input_mode,
             format,
             catch,
@@ -1176,7 +2800,7 @@ pub fn emmit_tag_tokens(
                 Some(Lit::Str(stri)) => stri.value(),
                 None => ident.to_string(),
                 // TODO proper error span
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             };
             let label = Literal::string(&label);
             let mut params = quote! {
@@ -1194,80 +2818,82 @@ pub fn emmit_tag_tokens(
 
             match flags {
                 Some(Lit::Str(flags)) => {
-                    let fn_ident: syn::Path =
-                        syn::parse_str(&flags.value()).expect("Error parsing function path.");
+                    let fn_ident: syn::Path = syn::parse_str(&flags.value())
+                        .map_err(|_| Error::invalid_path(flags.span(), &flags.value()))?;
                     params.extend(quote! { params.flags = Some( #fn_ident() ); });
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
 
             match preview {
                 Some(Lit::Str(c)) => {
-                    let var = Ident::new(&c.value(), ident.span());
+                    let var = lit_ident(c)?;
                     params.extend(quote! {{
                         params.preview = Some( imgui::ColorPreview::#var );
                     }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
 
-            match mode {
+            match picker_mode {
                 Some(Lit::Str(c)) => {
-                    let var = Ident::new(&c.value(), ident.span());
+                    let var = lit_ident(c)?;
                     params.extend(quote! {{
                         params.mode = Some( imgui::ColorPickerMode::#var );
                     }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
 
             match input_mode {
                 Some(Lit::Str(c)) => {
-                    let var = Ident::new(&c.value(), ident.span());
+                    let var = lit_ident(c)?;
                     params.extend(quote! {{
                         params.input_mode = Some( imgui::ColorEditInputMode::#var );
                     }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
 
             match format {
                 Some(Lit::Str(c)) => {
-                    let var = Ident::new(&c.value(), ident.span());
+                    let var = lit_ident(c)?;
                     params.extend(quote! {{
                         params.format = Some( imgui::ColorFormat::#var );
                     }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
 
-            let catch_ident =
-                catch_ident(attr, ident, catch.as_ref(), input_fields, fields, methods)?;
+            let catch_ident = catch_ident(ident, catch.as_ref(), input_fields, fields, methods)?;
 
-            match map {
-                None => {
+            let map = map_path(map.as_ref())?;
+            match mode {
+                Mode::Edit => {
+                    let elem = elem_tokens(ident, map.as_ref(), mode);
                     quote! {{
                         use imgui_ext::color::ColorPicker;
-                        let _ev = ColorPicker::build(ui, &mut ext.#ident, { #params ; params });
+                        let _ev = ColorPicker::build(ui, #elem, { #params ; params });
                         events.#catch_ident |= _ev;
+                        _ev
                     }}
                 }
-                Some(Lit::Str(map)) => {
-                    // TODO error handling
-                    let map_path: syn::Path =
-                        syn::parse_str(&map.value()).expect("Error parsing parth to function.");
+                Mode::ReadOnly => {
+                    // the color widgets take `&mut` to the value
+                    let elem = match map {
+                        Some(map) => quote!(#map(&mut ext.#ident.clone())),
+                        None => quote!(&mut ext.#ident.clone()),
+                    };
                     quote! {{
                         use imgui_ext::color::ColorPicker;
-                        let _ev = ColorPicker::build(ui, #map_path(&mut ext.#ident), { #params ; params });
-                        events.#catch_ident |= _ev;
+                        ColorPicker::build_readonly(ui, #elem, { #params ; params });
                     }}
                 }
-                _ => return Err(Error::invalid_format(attr.span())),
             }
         }
         Tag::ColorButton(ColorButton {
@@ -1283,7 +2909,7 @@ pub fn emmit_tag_tokens(
                 Some(Lit::Str(stri)) => stri.value(),
                 None => ident.to_string(),
                 // TODO proper error span
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             };
             let label = Literal::string(&label);
             let mut params = quote! {
@@ -1300,68 +2926,63 @@ pub fn emmit_tag_tokens(
 
             match input_mode {
                 Some(Lit::Str(c)) => {
-                    let var = Ident::new(&c.value(), ident.span());
+                    let var = lit_ident(c)?;
                     params.extend(quote! {{
                         params.input_mode = Some( imgui::ColorEditInputMode::#var );
                     }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
 
             match flags {
                 Some(Lit::Str(flags)) => {
-                    let fn_ident: syn::Path =
-                        syn::parse_str(&flags.value()).expect("Error parsing function path.");
+                    let fn_ident: syn::Path = syn::parse_str(&flags.value())
+                        .map_err(|_| Error::invalid_path(flags.span(), &flags.value()))?;
                     params.extend(quote! { params.flags = Some( #fn_ident() ); });
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
 
             match size {
                 Some(Lit::Str(size)) => {
-                    let ident = Ident::new(&size.value(), size.span());
+                    let ident = lit_path(size)?;
                     params.extend(quote! { params.size = Some( #ident().into() ); });
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
 
             match preview {
                 Some(Lit::Str(c)) => {
-                    let var = Ident::new(&c.value(), ident.span());
+                    let var = lit_ident(c)?;
                     params.extend(quote! {{
                         use imgui::ColorPreview;
                         params.preview = Some( ColorPreview::#var );
                     }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
 
-            let catch_ident =
-                catch_ident(attr, ident, catch.as_ref(), input_fields, fields, methods)?;
+            let catch_ident = catch_ident(ident, catch.as_ref(), input_fields, fields, methods)?;
 
-            match map {
-                None => {
-                    quote! {{
-                        use imgui_ext::color::ColorButton;
-                        let _ev = ColorButton::build(ui, ext.#ident, { #params ; params });
-                        events.#catch_ident |= _ev;
-                    }}
-                }
-                Some(Lit::Str(map)) => {
-                    // TODO error handling
-                    let map_path: syn::Path =
-                        syn::parse_str(&map.value()).expect("Error parsing parth to function.");
-                    quote! {{
-                        use imgui_ext::color::ColorButton;
-                        let _ev = ColorButton::build(ui, #map_path(ext.#ident), { #params ; params });
-                        events.#catch_ident |= _ev;
-                    }}
-                }
-                _ => return Err(Error::invalid_format(attr.span())),
+            let elem = match map_path(map.as_ref())? {
+                Some(map) => quote!(#map(ext.#ident)),
+                None => quote!(ext.#ident),
+            };
+            match mode {
+                Mode::Edit => quote! {{
+                    use imgui_ext::color::ColorButton;
+                    let _ev = ColorButton::build(ui, #elem, { #params ; params });
+                    events.#catch_ident |= _ev;
+                    _ev
+                }},
+                Mode::ReadOnly => quote! {{
+                    use imgui_ext::color::ColorButton;
+                    ColorButton::build_readonly(ui, #elem, { #params ; params });
+                }},
             }
         }
         Tag::Input(Input {
@@ -1377,7 +2998,7 @@ pub fn emmit_tag_tokens(
                 Some(Lit::Str(stri)) => stri.value(),
                 None => ident.to_string(),
                 // TODO proper error span
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             };
             let label = Literal::string(&label);
             let mut params = quote! {
@@ -1394,12 +3015,12 @@ pub fn emmit_tag_tokens(
 
             match size {
                 Some(Lit::Str(size)) => {
-                    let fn_ident: syn::Path =
-                        syn::parse_str(&size.value()).expect("Error parsing function path.");
+                    let fn_ident: syn::Path = syn::parse_str(&size.value())
+                        .map_err(|_| Error::invalid_path(size.span(), &size.value()))?;
                     params.extend(quote! {{ params.size = Some( #fn_ident().into() ); }});
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
 
             match step {
@@ -1411,11 +3032,11 @@ pub fn emmit_tag_tokens(
                     match (step_i64, step_f64) {
                         (Err(_), Ok(step)) => params.extend(quote!(params.step = Some(#step);)),
                         (Ok(step), _) => params.extend(quote!(params.step = Some(#step);)),
-                        _ => return Err(Error::parsing_error(step.span())),
+                        _ => return Err(Error::expected(step.span(), "a number")),
                     }
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a number")),
             }
 
             match step_fast {
@@ -1429,47 +3050,40 @@ pub fn emmit_tag_tokens(
                             params.extend(quote!(params.step_fast = Some(#step);))
                         }
                         (Ok(step), _) => params.extend(quote!(params.step_fast = Some(#step);)),
-                        _ => return Err(Error::parsing_error(step.span())),
+                        _ => return Err(Error::expected(step.span(), "a number")),
                     }
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a number")),
             }
 
             match flags {
                 Some(Lit::Str(flags)) => {
-                    let fn_ident: syn::Path =
-                        syn::parse_str(&flags.value()).expect("Error parsing function path.");
+                    let fn_ident: syn::Path = syn::parse_str(&flags.value())
+                        .map_err(|_| Error::invalid_path(flags.span(), &flags.value()))?;
                     params.extend(quote! { params.flags = Some( #fn_ident() ); });
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
 
             // TODO ????????
             params.extend(quote!(params));
 
-            let catch_ident =
-                catch_ident(attr, ident, catch.as_ref(), input_fields, fields, methods)?;
+            let catch_ident = catch_ident(ident, catch.as_ref(), input_fields, fields, methods)?;
 
-            match map {
-                None => quote!({
+            let elem = elem_tokens(ident, map_path(map.as_ref())?.as_ref(), mode);
+            match mode {
+                Mode::Edit => quote!({
                     use imgui_ext::input::Input;
-                    let _ev = Input::build(ui, &mut ext.#ident, { #params });
+                    let _ev = Input::build(ui, #elem, { #params });
                     events.#catch_ident |= _ev;
+                    _ev
+                }),
+                Mode::ReadOnly => quote!({
+                    use imgui_ext::input::Input;
+                    Input::build_readonly(ui, #elem, { #params });
                 }),
-                Some(Lit::Str(map)) => {
-                    // TODO error handling
-                    let map_path: syn::Path =
-                        syn::parse_str(&map.value()).expect("Error parsing parth to function.");
-
-                    quote!({
-                        use imgui_ext::input::Input;
-                        let _ev = Input::build(ui, #map_path (&mut ext.#ident), { #params });
-                        events.#catch_ident |= _ev;
-                    })
-                }
-                _ => return Err(Error::invalid_format(attr.span())),
             }
         }
         Tag::Drag(Drag {
@@ -1485,7 +3099,7 @@ pub fn emmit_tag_tokens(
             let label = match label {
                 Some(Lit::Str(stri)) => stri.value(),
                 None => ident.to_string(),
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             };
             let label = Literal::string(&label);
             let mut params = quote! {
@@ -1510,11 +3124,11 @@ pub fn emmit_tag_tokens(
                     match (min_i64, min_f64) {
                         (Err(_), Ok(min)) => params.extend(quote!(params.min = Some(#min);)),
                         (Ok(min), _) => params.extend(quote!(params.min = Some(#min);)),
-                        _ => return Err(Error::parsing_error(min.span())),
+                        _ => return Err(Error::expected(min.span(), "a number")),
                     }
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a number")),
             }
 
             match max {
@@ -1526,71 +3140,64 @@ pub fn emmit_tag_tokens(
                     match (max_i64, max_f64) {
                         (Err(_), Ok(max)) => params.extend(quote!(params.max = Some(#max);)),
                         (Ok(max), _) => params.extend(quote!(params.max = Some(#max);)),
-                        _ => return Err(Error::parsing_error(max.span())),
+                        _ => return Err(Error::expected(max.span(), "a number")),
                     }
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a number")),
             }
 
             match speed {
                 Some(Lit::Float(value)) => params.extend(quote! { params.speed = Some(#value); }),
                 Some(Lit::Str(value)) => match value.value().parse::<f32>() {
                     Ok(value) => params.extend(quote! { params.speed = Some(#value); }),
-                    Err(_) => return Err(Error::parsing_error(value.span())),
+                    Err(_) => return Err(Error::expected(value.span(), "a number")),
                 },
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a number")),
             }
             match power {
                 Some(Lit::Float(value)) => params.extend(quote! { params.power = Some(#value); }),
                 Some(Lit::Str(value)) => match value.value().parse::<f32>() {
                     Ok(value) => params.extend(quote! { params.power = Some(#value); }),
-                    Err(_) => return Err(Error::parsing_error(value.span())),
+                    Err(_) => return Err(Error::expected(value.span(), "a number")),
                 },
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a number")),
             }
             match format {
                 Some(Lit::Str(value)) => {
                     params.extend(quote!(params.format = Some(im_str!(#value));))
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
 
-            let catch_ident =
-                catch_ident(attr, ident, catch.as_ref(), input_fields, fields, methods)?;
+            let catch_ident = catch_ident(ident, catch.as_ref(), input_fields, fields, methods)?;
 
             params.extend(quote!(params));
-            match map {
-                None => quote!({
+            let elem = elem_tokens(ident, map_path(map.as_ref())?.as_ref(), mode);
+            match mode {
+                Mode::Edit => quote!({
                     use imgui_ext::drag::Drag;
-                    let _ev = Drag::build(ui, &mut ext.#ident, { #params });
+                    let _ev = Drag::build(ui, #elem, { #params });
                     events.#catch_ident |= _ev;
+                    _ev
+                }),
+                Mode::ReadOnly => quote!({
+                    use imgui_ext::drag::Drag;
+                    Drag::build_readonly(ui, #elem, { #params });
                 }),
-                Some(Lit::Str(map)) => {
-                    // TODO error handling
-                    let map_path: syn::Path =
-                        syn::parse_str(&map.value()).expect("Error parsing parth to function.");
-
-                    quote!({
-                        use imgui_ext::drag::Drag;
-                        let _ev = Drag::build(ui, #map_path(&mut ext.#ident), { #params });
-                        events.#catch_ident |= _ev;
-                    })
-                }
-                _ => return Err(Error::invalid_format(attr.span())),
             }
         }
         Tag::Button(Button { label, size, catch }) => {
             let label = match label {
                 Lit::Str(stri) => Literal::string(&stri.value()),
-                _ => return Err(Error::invalid_format(attr.span())),
+                lit => return Err(Error::expected(lit.span(), "a string literal")),
             };
 
             let catch = if let Some(Lit::Str(c)) = catch {
-                let id = Ident::new(&c.value(), ident.span());
+                let id = lit_ident(c)?;
                 let q = quote! { events.#id = _ev; };
                 fields.extend(quote! { pub #id: bool , });
                 methods.extend(quote! { pub fn #id(&self) -> bool { self.#id } });
@@ -1599,20 +3206,26 @@ pub fn emmit_tag_tokens(
                 quote!()
             };
 
-            if let Some(size) = size {
+            let button = if let Some(size) = size {
                 let size_fn = match size {
-                    Lit::Str(size) => Ident::new(&size.value(), size.span()),
-                    _ => return Err(Error::invalid_format(attr.span())),
+                    Lit::Str(size) => lit_path(size)?,
+                    lit => return Err(Error::expected(lit.span(), "a string literal")),
                 };
-                quote! {{
-                    let _ev = ui.button( imgui::im_str!( #label ), { #size_fn().into() } );
-                    #catch
-                }}
+                quote!(ui.button( imgui::im_str!( #label ), { #size_fn().into() } ))
             } else {
-                quote! {{
-                    let _ev = ui.small_button( imgui::im_str!( #label ) );
+                quote!(ui.small_button(imgui::im_str!( #label )))
+            };
+
+            match mode {
+                Mode::Edit => quote! {{
+                    let _ev = #button;
                     #catch
-                }}
+                    _ev
+                }},
+                // clicks are not reported
+                Mode::ReadOnly => quote! {{
+                    imgui_ext::readonly(ui, || #button);
+                }},
             }
         }
         Tag::BulletParent => {
@@ -1622,7 +3235,7 @@ pub fn emmit_tag_tokens(
             let text = match text {
                 Some(Lit::Str(text)) => Some(text),
                 None => None,
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             };
 
             if let Some(text) = text {
@@ -1646,7 +3259,7 @@ pub fn emmit_tag_tokens(
             let label = match label {
                 Some(Lit::Str(stri)) => stri.value(),
                 None => ident.to_string(),
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             };
             let label = Literal::string(&label);
             let min_max = match (min, max) {
@@ -1657,7 +3270,7 @@ pub fn emmit_tag_tokens(
                         .value()
                         .parse()
                         .map(Literal::i64_unsuffixed)
-                        .map_err(|_| Error::parsing_error(min.span()))?;
+                        .map_err(|_| Error::expected(min.span(), "a number"))?;
 
                     quote! { min: #min, max: #max }
                 }
@@ -1666,7 +3279,7 @@ pub fn emmit_tag_tokens(
                         .value()
                         .parse()
                         .map(Literal::f64_unsuffixed)
-                        .map_err(|_| Error::parsing_error(min.span()))?;
+                        .map_err(|_| Error::expected(min.span(), "a number"))?;
 
                     quote! { min: #min, max: #max }
                 }
@@ -1675,7 +3288,7 @@ pub fn emmit_tag_tokens(
                         .value()
                         .parse()
                         .map(Literal::i64_unsuffixed)
-                        .map_err(|_| Error::parsing_error(max.span()))?;
+                        .map_err(|_| Error::expected(max.span(), "a number"))?;
 
                     quote! { min: #min, max: #max }
                 }
@@ -1684,7 +3297,7 @@ pub fn emmit_tag_tokens(
                         .value()
                         .parse()
                         .map(Literal::f64_unsuffixed)
-                        .map_err(|_| Error::parsing_error(max.span()))?;
+                        .map_err(|_| Error::expected(max.span(), "a number"))?;
 
                     quote! { min: #min, max: #max }
                 }
@@ -1699,10 +3312,15 @@ pub fn emmit_tag_tokens(
                         (Ok(min), Ok(max), _, _) => quote! { min: #min, max: #max },
 
                         // Nope
-                        _ => return Err(Error::parsing_error(max.span())),
+                        _ => return Err(Error::expected(max.span(), "a number")),
                     }
                 }
-                _ => return Err(Error::invalid_format(attr.span())),
+                (min, _) => {
+                    return Err(Error::expected(
+                        min.span(),
+                        "numeric `min` and `max` of the same type",
+                    ))
+                }
             };
             let mut params = quote! {
                 use imgui_ext::slider::SliderParams as Params;
@@ -1719,105 +3337,151 @@ pub fn emmit_tag_tokens(
                     params.extend(quote!(params.format = Some( im_str!(#value) );))
                 }
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             }
             match power {
                 Some(Lit::Float(value)) => params.extend(quote!(params.power = Some(#value);)),
                 Some(Lit::Str(value)) => match value.value().parse::<f32>() {
                     Ok(value) => params.extend(quote! { params.power = Some(#value); }),
-                    Err(_) => return Err(Error::parsing_error(value.span())),
+                    Err(_) => return Err(Error::expected(value.span(), "a number")),
                 },
                 None => {}
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a number")),
             }
 
-            let catch_ident =
-                catch_ident(attr, ident, catch.as_ref(), input_fields, fields, methods)?;
+            let catch_ident = catch_ident(ident, catch.as_ref(), input_fields, fields, methods)?;
 
             params.extend(quote!(params));
-            match map {
-                None => quote!({
+            let elem = elem_tokens(ident, map_path(map.as_ref())?.as_ref(), mode);
+            match mode {
+                Mode::Edit => quote!({
                     use imgui_ext::slider::Slider;
-                    let _ev = Slider::build(ui, &mut ext.#ident, { #params });
+                    let _ev = Slider::build(ui, #elem, { #params });
                     events.#catch_ident |= _ev;
+                    _ev
+                }),
+                Mode::ReadOnly => quote!({
+                    use imgui_ext::slider::Slider;
+                    Slider::build_readonly(ui, #elem, { #params });
                 }),
-                Some(Lit::Str(map)) => {
-                    // TODO error handling
-                    let map_path: syn::Path =
-                        syn::parse_str(&map.value()).expect("Error parsing parth to function.");
-                    quote!({
-                        use imgui_ext::slider::Slider;
-                        let _ev = Slider::build(ui, #map_path(&mut ext.#ident), { #params });
-                        events.#catch_ident |= _ev;
-                    })
-                }
-                _ => return Err(Error::invalid_format(attr.span())),
             }
         }
         Tag::Checkbox(Checkbox { label, catch, map }) => {
             let label = match label {
                 Some(Lit::Str(lab)) => lab.value(),
                 None => ident.to_string(),
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             };
             let label = Literal::string(&label);
 
-            let catch_ident =
-                catch_ident(attr, ident, catch.as_ref(), input_fields, fields, methods)?;
+            let catch_ident = catch_ident(ident, catch.as_ref(), input_fields, fields, methods)?;
 
-            match map {
-                None => quote!({
+            let elem = elem_tokens(ident, map_path(map.as_ref())?.as_ref(), mode);
+            match mode {
+                Mode::Edit => quote!({
                     use imgui_ext::checkbox::Checkbox;
                     use imgui_ext::checkbox::CheckboxParams as Params;
                     use imgui::im_str;
-                    let _ev = Checkbox::build(ui, &mut ext.#ident, Params { label: im_str!(#label) });
+                    let _ev = Checkbox::build(ui, #elem, Params { label: im_str!(#label) });
                     events.#catch_ident |= _ev;
+                    _ev
+                }),
+                Mode::ReadOnly => quote!({
+                    use imgui_ext::checkbox::Checkbox;
+                    use imgui_ext::checkbox::CheckboxParams as Params;
+                    use imgui::im_str;
+                    Checkbox::build_readonly(ui, #elem, Params { label: im_str!(#label) });
                 }),
-                Some(Lit::Str(map)) => {
-                    // TODO error handling
-                    let map_path: syn::Path =
-                        syn::parse_str(&map.value()).expect("Error parsing parth to function.");
-                    quote!({
-                        use imgui_ext::checkbox::Checkbox;
-                        use imgui_ext::checkbox::CheckboxParams as Params;
-                        use imgui::im_str;
-                        let _ev = Checkbox::build(ui, #map_path(&mut ext.#ident), Params { label: im_str!(#label) });
-                        events.#catch_ident |= _ev;
-                    })
-                }
-                _ => return Err(Error::invalid_format(attr.span())),
             }
         }
         Tag::Nested(Nested { catch, map }) => {
-            let catch_ident = catch_ident_nested(
-                attr,
-                _ty,
-                ident,
-                catch.as_ref(),
-                input_fields,
-                fields,
-                methods,
-            )?;
-
-            match map {
-                None => {
-                    quote! {{
-                        use imgui_ext::Gui;
-                        let _ev = Gui::draw_gui(ui, &mut ext.#ident);
-                        events.#catch_ident = _ev;
-                    }}
-                }
-                Some(Lit::Str(map)) => {
-                    // TODO error handling
-                    let map_path: syn::Path =
-                        syn::parse_str(&map.value()).expect("Error parsing parth to function.");
-                    quote! {{
-                        use imgui_ext::Gui;
-                        let _ev = Gui::draw_gui(ui, #map_path(&mut ext.#ident));
-                        events.#catch_ident = _ev;
-                    }}
+            let catch_ident =
+                catch_ident_nested(_ty, ident, catch.as_ref(), input_fields, fields, methods)?;
+
+            let elem = elem_tokens(ident, map_path(map.as_ref())?.as_ref(), mode);
+            match mode {
+                Mode::Edit => quote! {{
+                    use imgui_ext::Gui;
+                    let _ev = Gui::draw_gui(ui, #elem);
+                    events.#catch_ident = _ev;
+                }},
+                Mode::ReadOnly => quote! {{
+                    use imgui_ext::Gui;
+                    Gui::draw_gui_readonly(ui, #elem);
+                }},
+            }
+        }
+        Tag::Menu(Menu { label, catch, map }) => {
+            let label = match label {
+                Some(Lit::Str(lab)) => lab.value(),
+                None => ident.to_string(),
+                Some(lit) => return Err(Error::invalid_format(lit.span())),
+            };
+            let label = Literal::string(&label);
+
+            let catch_ident =
+                catch_ident_nested(_ty, ident, catch.as_ref(), input_fields, fields, methods)?;
+
+            let elem = elem_tokens(ident, map_path(map.as_ref())?.as_ref(), mode);
+            let draw = match mode {
+                Mode::Edit => quote! {
+                    let _ev = Gui::draw_gui(ui, #elem);
+                    events.#catch_ident = _ev;
+                },
+                Mode::ReadOnly => quote!(Gui::draw_gui_readonly(ui, #elem);),
+            };
+
+            quote! {{
+                use imgui_ext::Gui;
+                imgui_ext::menu::menu(ui, imgui::im_str!(#label), || { #draw });
+            }}
+        }
+        Tag::MenuItem(MenuItem {
+            label,
+            shortcut,
+            catch,
+            map,
+        }) => {
+            let label = match label {
+                Some(Lit::Str(lab)) => lab.value(),
+                None => ident.to_string(),
+                Some(lit) => return Err(Error::invalid_format(lit.span())),
+            };
+            let label = Literal::string(&label);
+
+            let mut params = quote! {
+                use imgui_ext::menu::MenuItemParams as Params;
+                use imgui::im_str;
+                let mut params = Params {
+                    label: im_str!(#label),
+                    shortcut: None,
+                    enabled: true,
+                };
+            };
+
+            match shortcut {
+                Some(Lit::Str(shortcut)) => {
+                    params.extend(quote!(params.shortcut = Some(im_str!(#shortcut));))
                 }
-                _ => return Err(Error::invalid_format(attr.span())),
+                None => {}
+                Some(lit) => return Err(Error::invalid_format(lit.span())),
+            }
+            params.extend(quote!(params));
+
+            let catch_ident = catch_ident(ident, catch.as_ref(), input_fields, fields, methods)?;
+
+            let elem = elem_tokens(ident, map_path(map.as_ref())?.as_ref(), mode);
+            match mode {
+                Mode::Edit => quote!({
+                    use imgui_ext::menu::MenuItem;
+                    let _ev = MenuItem::build(ui, #elem, { #params });
+                    events.#catch_ident |= _ev;
+                    _ev
+                }),
+                Mode::ReadOnly => quote!({
+                    use imgui_ext::menu::MenuItem;
+                    MenuItem::build_readonly(ui, #elem, { #params });
+                }),
             }
         }
         Tag::Display(Display {
@@ -1828,14 +3492,14 @@ pub fn emmit_tag_tokens(
             let label = match label {
                 Some(Lit::Str(lab)) => lab.value(),
                 None => ident.to_string(),
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             };
             let label = Literal::string(&label);
 
             let display = match display {
                 Some(Lit::Str(disp)) => Some(disp.value()),
                 None => None,
-                _ => return Err(Error::invalid_format(attr.span())),
+                Some(lit) => return Err(Error::expected(lit.span(), "a string literal")),
             };
 
             let display = if let Some(display) = display {
@@ -1860,11 +3524,474 @@ pub fn emmit_tag_tokens(
         }
     };
 
+    // report the widget to the testing harness (a no-op without the feature)
+    match (tag.item(ident), tag.container(ident)) {
+        (Some(("button", label)), _) => Ok(quote! {{
+            let _ev = { #tokens };
+            imgui_ext::__item(ui, "button", #label, &|| None);
+            _ev
+        }}),
+        (Some((kind, label)), _) => Ok(quote! {{
+            let _ev = { #tokens };
+            imgui_ext::__item(ui, #kind, #label, &|| {
+                #[allow(unused_imports)]
+                use imgui_ext::__value::{DebugValue, NoValue, Value};
+                (&&Value(&ext.#ident)).value()
+            });
+            _ev
+        }}),
+        (None, Some((kind, label))) => Ok(quote! {{
+            let _scope = imgui_ext::__scope(ui, #kind, #label);
+            #tokens
+        }}),
+        (None, None) => Ok(tokens),
+    }
+}
+
+/// Output source code of the `Visit` trait for a given field and one of the
+/// parsed `Tag`s. Errors in the annotations are reported by
+/// `emmit_tag_tokens`, so this function skips the invalid params.
+///
+/// With `describe`, the tokens of `Visit::describe` are emmited instead
+/// (there is no value to visit, only the types).
+pub fn emmit_visit_tokens(
+    ident: &Ident,
+    ty: &Type,
+    tag: &Tag,
+    describe: bool,
+) -> Result<TokenStream, Error> {
+    let widget = |kind: &str, label: String| {
+        let field = Literal::string(&ident.to_string());
+        let ty = Literal::string(&type_name(ty));
+        let params = tag.params().into_iter().map(|(name, lit)| {
+            let param = param_tokens(name, lit);
+            quote!((#name, #param))
+        });
+        quote! {
+            imgui_ext::visit::Widget {
+                kind: #kind,
+                label: #label,
+                field: #field,
+                ty: #ty,
+                params: &[ #( #params ),* ],
+            }
+        }
+    };
+    let map = |map: &Option<Lit>| {
+        map_path(map.as_ref()).map(|map| elem_tokens(ident, map.as_ref(), Mode::Edit))
+    };
+    let content = |tags: &Option<Vec<Tag>>| {
+        tags.iter()
+            .flatten()
+            .map(|tag| emmit_visit_tokens(ident, ty, tag, describe))
+            .collect::<Result<Vec<_>, Error>>()
+    };
+
+    let tokens = match tag {
+        Tag::None | Tag::BulletParent => quote!(),
+        Tag::Common(tag, _) => emmit_visit_tokens(ident, ty, tag, describe)?,
+        Tag::Separator => {
+            let widget = widget("separator", String::new());
+            quote!(visitor.widget(&#widget, imgui_ext::visit::ValueMut::None);)
+        }
+        Tag::NewLine => {
+            let widget = widget("new_line", String::new());
+            quote!(visitor.widget(&#widget, imgui_ext::visit::ValueMut::None);)
+        }
+        Tag::Text(Text { lit }) | Tag::TextWrap(Text { lit }) => {
+            let kind = if let Tag::Text(_) = tag {
+                "text"
+            } else {
+                "text_wrap"
+            };
+            let label = match lit {
+                Lit::Str(lit) => lit.value(),
+                _ => ident.to_string(),
+            };
+            let widget = widget(kind, label);
+            quote!(visitor.widget(&#widget, imgui_ext::visit::ValueMut::None);)
+        }
+        Tag::Bullet(Bullet { text }) => {
+            let label = match text {
+                Some(Lit::Str(text)) => text.value(),
+                _ => String::new(),
+            };
+            let widget = widget("bullet", label);
+            quote!(visitor.widget(&#widget, imgui_ext::visit::ValueMut::None);)
+        }
+        Tag::Nested(Nested { map: elem, .. }) | Tag::Menu(Menu { map: elem, .. }) => {
+            let (kind, label) = tag.container(ident).unwrap();
+            let widget = widget(kind, label);
+            let visit = match (describe, map_path(elem.as_ref())?) {
+                (false, _) => {
+                    let elem = map(elem)?;
+                    quote!(imgui_ext::Visit::visit(#elem, visitor);)
+                }
+                (true, None) => quote!(<#ty as imgui_ext::Visit>::describe(visitor);),
+                (true, Some(map)) => quote!(imgui_ext::visit::describe_mapped(#map, visitor);),
+            };
+            quote! {{
+                let _widget = #widget;
+                visitor.begin(&_widget);
+                #visit
+                visitor.end(&_widget);
+            }}
+        }
+        Tag::Tree(Tree { node: tags, .. })
+        | Tag::Child(Child { content: tags, .. })
+        | Tag::Popup(Popup { content: tags, .. }) => {
+            let (kind, label) = tag.container(ident).unwrap();
+            let widget = widget(kind, label);
+            let content = content(tags)?;
+            quote! {{
+                let _widget = #widget;
+                visitor.begin(&_widget);
+                #( #content )*
+                visitor.end(&_widget);
+            }}
+        }
+        Tag::Vars(Vars { content: tags, .. }) => {
+            let widget = widget("vars", ident.to_string());
+            let content = content(tags)?;
+            quote! {{
+                let _widget = #widget;
+                visitor.begin(&_widget);
+                #( #content )*
+                visitor.end(&_widget);
+            }}
+        }
+        Tag::Button(_) | Tag::Image(_) | Tag::ImageButton(_) => {
+            let (kind, label) = tag.item(ident).unwrap_or(("button", ident.to_string()));
+            let widget = widget(kind, label);
+            quote!(visitor.widget(&#widget, imgui_ext::visit::ValueMut::None);)
+        }
+        Tag::Display(_)
+        | Tag::Checkbox(_)
+        | Tag::Input(_)
+        | Tag::Slider(_)
+        | Tag::Drag(_)
+        | Tag::MenuItem(_)
+        | Tag::Progress(_)
+        | Tag::ColorButton(_)
+        | Tag::ColorPicker(_)
+        | Tag::ColorEdit(_) => {
+            let (kind, label) = tag.item(ident).unwrap();
+            let widget = widget(kind, label);
+            if describe {
+                return Ok(quote!(visitor.widget(&#widget, imgui_ext::visit::ValueMut::None);));
+            }
+            let elem = match tag {
+                Tag::Checkbox(Checkbox { map: elem, .. })
+                | Tag::Input(Input { map: elem, .. })
+                | Tag::Slider(Slider { map: elem, .. })
+                | Tag::Drag(Drag { map: elem, .. })
+                | Tag::MenuItem(MenuItem { map: elem, .. })
+                | Tag::ColorButton(ColorButton { map: elem, .. })
+                | Tag::ColorPicker(ColorPicker { map: elem, .. })
+                | Tag::ColorEdit(ColorEdit { map: elem, .. }) => map(elem)?,
+                _ => map(&None)?,
+            };
+            quote! {{
+                #[allow(unused_imports)]
+                use imgui_ext::visit::{IntoValue, NoValue, Value};
+                visitor.widget(&#widget, (&&Value::new(#elem)).value());
+            }}
+        }
+    };
+
     Ok(tokens)
 }
 
+// Tokens of an `imgui_ext::visit::Param`. Numbers written as strings (such as
+// `min = "-1.0"`) are parsed, except for the labels.
+fn param_tokens(name: &str, lit: &Lit) -> TokenStream {
+    match lit {
+        Lit::Bool(b) => quote!(imgui_ext::visit::Param::Bool(#b)),
+        Lit::Int(int) => match int.base10_parse::<i64>() {
+            Ok(int) => int_tokens(int),
+            Err(_) => quote!(imgui_ext::visit::Param::Str(stringify!(#int))),
+        },
+        Lit::Float(float) => match float.base10_parse::<f64>() {
+            Ok(float) => float_tokens(float),
+            Err(_) => quote!(imgui_ext::visit::Param::Str(stringify!(#float))),
+        },
+        Lit::Str(s) => {
+            let s = s.value();
+            match (name, s.parse::<i64>(), s.parse::<f64>()) {
+                ("label", _, _) => quote!(imgui_ext::visit::Param::Str(#s)),
+                (_, Ok(int), _) => int_tokens(int),
+                (_, _, Ok(float)) => float_tokens(float),
+                _ => quote!(imgui_ext::visit::Param::Str(#s)),
+            }
+        }
+        lit => quote!(imgui_ext::visit::Param::Str(stringify!(#lit))),
+    }
+}
+
+// Negative numbers are emmited as a negation of the absolute value.
+fn int_tokens(int: i64) -> TokenStream {
+    let abs = Literal::u64_unsuffixed(int.unsigned_abs());
+    if int < 0 {
+        quote!(imgui_ext::visit::Param::Int(-#abs))
+    } else {
+        quote!(imgui_ext::visit::Param::Int(#abs))
+    }
+}
+
+fn float_tokens(float: f64) -> TokenStream {
+    let abs = Literal::f64_unsuffixed(float.abs());
+    if float < 0.0 {
+        quote!(imgui_ext::visit::Param::Float(-#abs))
+    } else {
+        quote!(imgui_ext::visit::Param::Float(#abs))
+    }
+}
+
+/// Name of a type, with the whitespace around punctuation removed.
+fn type_name(ty: &Type) -> String {
+    let mut name = String::new();
+    print_tokens(quote!(#ty), &mut name);
+    name
+}
+
+/// Whether the tag renders the UI of the type of the field (an unmapped
+/// `nested` or `menu`), either directly or inside of a container.
+pub fn is_nested(tag: &Tag) -> bool {
+    match tag {
+        Tag::Nested(Nested { map: None, .. }) | Tag::Menu(Menu { map: None, .. }) => true,
+        Tag::Common(tag, _) => is_nested(tag),
+        Tag::Tree(Tree { node: tags, .. })
+        | Tag::Child(Child { content: tags, .. })
+        | Tag::Popup(Popup { content: tags, .. })
+        | Tag::Vars(Vars { content: tags, .. }) => tags.iter().flatten().any(is_nested),
+        _ => false,
+    }
+}
+
+/// Source of the `#[imgui(...)]` annotation (without the `imgui(` and `)`),
+/// printed from its tokens.
+pub fn annotation_source(attr: &Attribute) -> String {
+    let mut tokens = attr.tokens.clone().into_iter();
+    let mut source = String::new();
+    match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Group(ref group)), None)
+            if group.delimiter() == Delimiter::Parenthesis =>
+        {
+            print_tokens(group.stream(), &mut source)
+        }
+        _ => print_tokens(attr.tokens.clone(), &mut source),
+    }
+    source
+}
+
+/// Prints the tokens the way they are usually written: separators (`,`, `=`
+/// and `+`) are followed by a space, words are separated by a space and no
+/// other whitespace is added.
+fn print_tokens(tokens: TokenStream, out: &mut String) {
+    // whether the last printed token was an identifier or a literal
+    let mut word = false;
+    // whether the last printed token was a joint punctuation (e.g. `:` in `::`)
+    let mut joint = false;
+    // whether the last printed token was the identifier of a lifetime
+    let mut lifetime = false;
+
+    for token in tokens {
+        match token {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::None => ("", ""),
+                };
+                if lifetime {
+                    out.push(' ');
+                }
+                out.push_str(open);
+                print_tokens(group.stream(), out);
+                out.truncate(out.trim_end().len());
+                out.push_str(close);
+                word = false;
+            }
+            TokenTree::Ident(ident) => {
+                if word {
+                    out.push(' ');
+                }
+                lifetime = joint && out.ends_with('\'');
+                out.push_str(&ident.to_string());
+                word = true;
+                joint = false;
+                continue;
+            }
+            TokenTree::Literal(lit) => {
+                if word {
+                    out.push(' ');
+                }
+                out.push_str(&lit.to_string());
+                word = true;
+            }
+            TokenTree::Punct(punct) => {
+                let ch = punct.as_char();
+                let alone = punct.spacing() == Spacing::Alone;
+                match ch {
+                    ',' if alone => out.push_str(", "),
+                    '=' | '+' if alone && !joint => {
+                        out.push(' ');
+                        out.push(ch);
+                        out.push(' ');
+                    }
+                    _ => out.push(ch),
+                }
+                joint = !alone;
+                word = false;
+                lifetime = false;
+                continue;
+            }
+        }
+        joint = false;
+        lifetime = false;
+    }
+}
+
+/// Output source code for the buttons of the `actions(...)` attribute (and of
+/// the `#[imgui_ext::actions]` impl blocks). Buttons of methods without
+/// arguments are placed in the same line.
+pub fn emmit_actions(
+    actions: &[Action],
+    fields: &mut TokenStream,
+    methods: &mut TokenStream,
+    input_fields: &mut HashSet<String>,
+    args_field: Option<&Ident>,
+    mode: Mode,
+) -> Result<TokenStream, Error> {
+    let mut tokens = TokenStream::new();
+    // the values of the arguments are kept between frames in a field of the
+    // struct, with a tuple for each method taking arguments
+    let mut args_index = 0;
+    let mut same_line = false;
+
+    for Action {
+        method,
+        label,
+        confirm,
+        args,
+    } in actions.iter()
+    {
+        let text = match label {
+            Some(Lit::Str(label)) => label.value(),
+            Some(lit) => return Err(Error::invalid_format(lit.span())),
+            None => method.to_string(),
+        };
+        let label = Literal::string(&text);
+
+        if mode == Mode::Edit {
+            if !input_fields.insert(method.to_string()) {
+                return Err(Error::already_defined(method.span()));
+            }
+            fields.extend(quote! { pub #method: bool , });
+            methods.extend(quote! { pub fn #method(&self) -> bool { self.#method } });
+        }
+
+        if same_line && args.is_empty() {
+            tokens.extend(quote!(ui.same_line(0.0);));
+        }
+        same_line = args.is_empty();
+
+        let mut values = Vec::new();
+        if mode == Mode::Edit && !args.is_empty() {
+            let field = args_field.ok_or_else(|| Error::missing_param(method.span(), "args"))?;
+            let method_index = syn::Index::from(args_index);
+            args_index += 1;
+
+            for (i, (arg, ty)) in args.iter().enumerate() {
+                let index = syn::Index::from(i);
+                let arg_label = Literal::string(&format!("{}##{}", arg, method));
+                let widget = match ty {
+                    Type::Path(path) if path.path.is_ident("bool") => quote! {
+                        imgui_ext::checkbox::Checkbox::build(
+                            ui,
+                            &mut ext.#field.#method_index.#index,
+                            imgui_ext::checkbox::CheckboxParams {
+                                label: im_str!(#arg_label),
+                            },
+                        );
+                    },
+                    _ => quote! {
+                        imgui_ext::input::Input::build(
+                            ui,
+                            &mut ext.#field.#method_index.#index,
+                            imgui_ext::input::InputParams {
+                                label: im_str!(#arg_label),
+                                step: None,
+                                step_fast: None,
+                                flags: None,
+                                size: None,
+                            },
+                        );
+                    },
+                };
+                tokens.extend(widget);
+                values.push(quote!(ext.#field.#method_index.#index.clone()));
+            }
+        }
+
+        let button = action_button(&label, quote!([0.0, 0.0]));
+        let call = quote! {
+            events.#method = true;
+            let _ = ext.#method( #( #values ),* );
+        };
+        let action = match (mode, confirm) {
+            (Mode::Edit, None) => quote! {
+                if #button {
+                    #call
+                }
+            },
+            (Mode::Edit, Some(Lit::Str(confirm))) => {
+                let title = Literal::string(&format!("{}##{}", text, method));
+                let ok = action_button(&Literal::string("OK"), quote!([120.0, 0.0]));
+                let cancel = action_button(&Literal::string("Cancel"), quote!([120.0, 0.0]));
+                quote! {
+                    if #button {
+                        ui.open_popup(im_str!(#title));
+                    }
+                    ui.popup_modal(im_str!(#title)).build(|| {
+                        ui.text(#confirm);
+                        ui.separator();
+                        if #ok {
+                            #call
+                            ui.close_current_popup();
+                        }
+                        ui.same_line(0.0);
+                        if #cancel {
+                            ui.close_current_popup();
+                        }
+                    });
+                }
+            }
+            (_, Some(Lit::Str(_))) | (Mode::ReadOnly, None) => quote! {
+                imgui_ext::readonly(ui, || #button);
+            },
+            (_, Some(lit)) => return Err(Error::invalid_format(lit.span())),
+        };
+        tokens.extend(action);
+    }
+
+    Ok(quote! {{
+        use imgui::im_str;
+        #tokens
+    }})
+}
+
+// Button of an action, reported to the testing harness.
+fn action_button(label: &Literal, size: TokenStream) -> TokenStream {
+    quote! {{
+        let _ev = ui.button(im_str!(#label), #size);
+        imgui_ext::__item(ui, "button", #label, &|| None);
+        _ev
+    }}
+}
+
 fn catch_ident(
-    attr: &Attribute,
     field: &Ident,
     catch: Option<&Lit>,
     field_set: &mut HashSet<String>,
@@ -1873,7 +4000,7 @@ fn catch_ident(
 ) -> Result<Ident, Error> {
     match catch {
         Some(Lit::Str(lit)) => {
-            let ident = Ident::new(&lit.value(), field.span());
+            let ident = lit_ident(lit)?;
 
             fields.extend(quote! { pub #ident: bool , });
             methods.extend(quote! { pub fn #ident(&self) -> bool { self.#ident } });
@@ -1893,13 +4020,12 @@ fn catch_ident(
             Ok(field.clone())
         }
 
-        _ => return Err(Error::invalid_format(attr.span())),
+        Some(lit) => Err(Error::expected(lit.span(), "a string literal")),
     }
 }
 
 // TODO code repetition bad nono FIXME naw
 fn catch_ident_nested(
-    attr: &Attribute,
     _ty: &Type,
     field: &Ident,
     catch: Option<&Lit>,
@@ -1911,7 +4037,7 @@ fn catch_ident_nested(
 
     match catch {
         Some(Lit::Str(lit)) => {
-            let ident = Ident::new(&lit.value(), field.span());
+            let ident = lit_ident(lit)?;
 
             fields.extend(quote! { pub #ident: <#tp as imgui_ext::Gui>::Events , });
             methods.extend(
@@ -1933,6 +4059,6 @@ fn catch_ident_nested(
             Ok(field.clone())
         }
 
-        _ => return Err(Error::invalid_format(attr.span())),
+        Some(lit) => Err(Error::expected(lit.span(), "a string literal")),
     }
 }
@@ -0,0 +1,302 @@
+//!
+//! Highlights the fields that differ from a baseline value (such as the
+//! defaults), with a button to revert each of them.
+//!
+//! [`UiExt::draw_gui_with_baseline`] renders the UI of a value, coloring the
+//! text of every field whose value differs from the baseline, followed by a
+//! `Revert` button. The paths of the dirty fields (the names of the nested
+//! fields joined with dots, such as `"light.color"`) are reported in the
+//! returned [`BaselineEvents`], and by the derived events struct
+//! (`dirty_fields()` and `is_dirty(path)`), which is empty when the value is
+//! drawn without a baseline.
+//!
+//! Fields are compared with `PartialEq` and reverted with `Clone`. Fields of
+//! types that don't implement them are never dirty (nor reverted). Nested
+//! types are compared field by field, so the derived `Diff` implementation is
+//! bounded on the nested types implementing `Diff` (types with a hand-written
+//! `Gui` implementation don't, unless they implement it by hand too).
+//!
+//! # Example
+//!
+//! ```
+//! use imgui_ext::UiExt;
+//!
+//! #[derive(imgui_ext::Gui, Clone, Default)]
+//! struct Light {
+//!     #[imgui(slider(min = 0.0, max = 10.0))]
+//!     intensity: f32,
+//!     #[imgui(color(edit))]
+//!     color: [f32; 3],
+//! }
+//!
+//! # fn frame(ui: &imgui::Ui, light: &mut Light) {
+//! let defaults = Light::default();
+//! let events = ui.draw_gui_with_baseline(light, &defaults);
+//!
+//! for field in events.dirty() {
+//!     println!("{} differs from the defaults", field);
+//! }
+//! if events.events.is_dirty("color") {
+//!     println!("the color differs from the defaults");
+//! }
+//! # }
+//! ```
+//!
+//! [`UiExt::draw_gui_with_baseline`]: ../trait.UiExt.html#tymethod.draw_gui_with_baseline
+//! [`BaselineEvents`]: ./struct.BaselineEvents.html
+use imgui::{im_str, ColorStackToken, StyleColor, Ui};
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use crate::Gui;
+
+/// Color of the text of the dirty fields.
+pub const COLOR: [f32; 4] = [1.0, 0.6, 0.2, 1.0];
+
+/// Comparison of the annotated fields of a value with a baseline.
+///
+/// Implemented by the derive macro.
+pub trait Diff {
+    /// Pushes into `dirty` the paths of the fields of `ext` that differ from
+    /// `base`, prefixed with `path`.
+    fn diff(ext: &Self, base: &Self, path: &str, dirty: &mut Vec<String>);
+
+    /// Replaces the field at `path` with its value in `base`. Returns `false`
+    /// if there is no such field, or it can't be cloned.
+    fn revert(ext: &mut Self, base: &Self, path: &str) -> bool;
+}
+
+impl<T: Diff> Diff for Option<T> {
+    fn diff(ext: &Self, base: &Self, path: &str, dirty: &mut Vec<String>) {
+        match (ext, base) {
+            (Some(ext), Some(base)) => T::diff(ext, base, path, dirty),
+            (None, None) => {}
+            _ => dirty.push(path.to_string()),
+        }
+    }
+
+    fn revert(ext: &mut Self, base: &Self, path: &str) -> bool {
+        match (ext, base) {
+            (Some(ext), Some(base)) => T::revert(ext, base, path),
+            _ => false,
+        }
+    }
+}
+
+impl<T: Diff> Diff for Box<T> {
+    #[inline]
+    fn diff(ext: &Self, base: &Self, path: &str, dirty: &mut Vec<String>) {
+        T::diff(ext, base, path, dirty)
+    }
+
+    #[inline]
+    fn revert(ext: &mut Self, base: &Self, path: &str) -> bool {
+        T::revert(ext, base, path)
+    }
+}
+
+/// Paths of the fields of `ext` that differ from `base`.
+pub fn dirty<T: Diff>(ext: &T, base: &T) -> Vec<String> {
+    let mut dirty = Vec::new();
+    T::diff(ext, base, "", &mut dirty);
+    dirty
+}
+
+/// Events reporting the fields that differ from the baseline. Implemented by
+/// the derived events struct.
+pub trait DirtyEvents {
+    fn set_dirty(&mut self, dirty: Vec<String>);
+}
+
+/// Events returned by `UiExt::draw_gui_with_baseline`.
+pub struct BaselineEvents<E> {
+    /// Events of the value.
+    pub events: E,
+    dirty: Vec<String>,
+    reverted: Vec<String>,
+}
+
+impl<E> BaselineEvents<E> {
+    /// Paths of the fields that differ from the baseline (at the end of the
+    /// frame).
+    pub fn dirty(&self) -> &[String] {
+        &self.dirty
+    }
+
+    /// The field at `path` differs from the baseline.
+    pub fn is_dirty(&self, path: &str) -> bool {
+        self.dirty.iter().any(|dirty| dirty == path)
+    }
+
+    /// Paths of the fields reverted with their button during this frame.
+    pub fn reverted(&self) -> &[String] {
+        &self.reverted
+    }
+}
+
+thread_local! {
+    // Dirty fields (if `draw_gui` is running).
+    static BASELINE: RefCell<Option<Baseline>> = RefCell::new(None);
+}
+
+struct Baseline {
+    dirty: HashSet<String>,
+    // path of the field being drawn, with the pushed colors (of the visible
+    // dirty fields)
+    path: Vec<(&'static str, Option<ColorStackToken>)>,
+    // paths of the clicked revert buttons
+    clicked: Vec<String>,
+}
+
+impl Baseline {
+    fn path(&self) -> String {
+        let path: Vec<_> = self.path.iter().map(|(field, _)| *field).collect();
+        path.join(".")
+    }
+}
+
+/// Renders the UI of `ext`, highlighting the fields that differ from `base`.
+pub fn draw_gui<T>(ui: &Ui, ext: &mut T, base: &T) -> BaselineEvents<T::Events>
+where
+    T: Gui + Diff,
+    T::Events: DirtyEvents,
+{
+    let baseline = Baseline {
+        dirty: dirty(ext, base).into_iter().collect(),
+        path: Vec::new(),
+        clicked: Vec::new(),
+    };
+    let prev = BASELINE.with(|b| b.borrow_mut().replace(baseline));
+    let mut events = T::draw_gui(ui, ext);
+    let baseline = BASELINE.with(|b| std::mem::replace(&mut *b.borrow_mut(), prev));
+
+    let mut reverted = Vec::new();
+    for path in baseline.map(|b| b.clicked).unwrap_or_default() {
+        if T::revert(ext, base, &path) {
+            reverted.push(path);
+        }
+    }
+
+    let dirty = dirty(ext, base);
+    events.set_dirty(dirty.clone());
+    BaselineEvents {
+        events,
+        dirty,
+        reverted,
+    }
+}
+
+/// Called before the widgets of a field.
+pub(crate) fn begin(ui: &Ui, field: &'static str, visible: bool) {
+    BASELINE.with(|b| {
+        if let Some(b) = b.borrow_mut().as_mut() {
+            b.path.push((field, None));
+            let colors = if visible && b.dirty.contains(&b.path()) {
+                Some(ui.push_style_colors(&[(StyleColor::Text, COLOR)]))
+            } else {
+                None
+            };
+            b.path.last_mut().unwrap().1 = colors;
+        }
+    });
+}
+
+/// Called after the widgets of a field. Renders the revert button of dirty
+/// fields.
+pub(crate) fn end(ui: &Ui) {
+    BASELINE.with(|b| {
+        if let Some(b) = b.borrow_mut().as_mut() {
+            let path = b.path();
+            if let Some((_, Some(colors))) = b.path.pop() {
+                colors.pop(ui);
+                ui.same_line(0.0);
+                let id = ui.push_id(path.as_str());
+                if ui.small_button(im_str!("Revert")) {
+                    b.clicked.push(path);
+                }
+                id.pop(ui);
+            }
+        }
+    });
+}
+
+// Used by the derived code to compare and revert the fields, when their types
+// implement `PartialEq` and `Clone` (autoref specialization).
+#[doc(hidden)]
+pub mod __diff {
+    use std::cell::Cell;
+
+    pub struct Field<'a, T>(pub &'a T, pub &'a T);
+
+    pub trait PartialEqField {
+        fn differs(&self) -> bool;
+    }
+
+    pub trait NoPartialEq {
+        fn differs(&self) -> bool;
+    }
+
+    impl<T: PartialEq> PartialEqField for &Field<'_, T> {
+        fn differs(&self) -> bool {
+            self.0 != self.1
+        }
+    }
+
+    impl<T> NoPartialEq for Field<'_, T> {
+        fn differs(&self) -> bool {
+            false
+        }
+    }
+
+    pub struct Revert<'a, T>(Cell<Option<&'a mut T>>, &'a T);
+
+    impl<'a, T> Revert<'a, T> {
+        pub fn new(ext: &'a mut T, base: &'a T) -> Self {
+            Revert(Cell::new(Some(ext)), base)
+        }
+    }
+
+    pub trait CloneRevert {
+        fn revert(&self) -> bool;
+    }
+
+    pub trait NoRevert {
+        fn revert(&self) -> bool;
+    }
+
+    impl<T: Clone> CloneRevert for &Revert<'_, T> {
+        fn revert(&self) -> bool {
+            match self.0.take() {
+                Some(ext) => {
+                    *ext = self.1.clone();
+                    true
+                }
+                None => false,
+            }
+        }
+    }
+
+    impl<T> NoRevert for Revert<'_, T> {
+        fn revert(&self) -> bool {
+            false
+        }
+    }
+
+    pub fn join(path: &str, field: &str) -> String {
+        if path.is_empty() {
+            field.to_string()
+        } else {
+            format!("{}.{}", path, field)
+        }
+    }
+
+    // "light.color" -> Some("color") (for the field "light")
+    pub fn strip<'a>(path: &'a str, field: &str) -> Option<&'a str> {
+        if path.starts_with(field) && path[field.len()..].starts_with('.') {
+            Some(&path[field.len() + 1..])
+        } else {
+            None
+        }
+    }
+}
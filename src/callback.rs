@@ -0,0 +1,80 @@
+//!
+//! Params used to call methods of the struct from the generated `draw_gui`,
+//! right after a widget reports an input event, instead of reading the event
+//! from the returned type afterwards:
+//!
+//! * `on_change = "..."` on the annotations that modify the value of the field
+//!   (`checkbox`, `input`, `slider`, `drag`, `color(...)` and `menu_item`).
+//!   When the annotation has a `validate` function, it's only called for valid
+//!   values.
+//! * `on_click = "..."` on `button(...)` annotations.
+//!
+//! The value is either the name of a method of the struct taking `&mut self`,
+//! or a path to a function implementing [`Callback`]:
+//!
+//! * `fn(&mut Self)`
+//! * `fn(&mut Self, T)`, where `T` is the event. For `on_change` it's the new
+//!   value of the field (which must implement `Clone`). For `on_click` it's
+//!   `()`.
+//!
+//! The input events are still reported in the returned type.
+//!
+//! ## Example
+//!
+//! ```
+//! #[derive(imgui_ext::Gui)]
+//! struct Camera {
+//!     #[imgui(slider(min = 10.0, max = 120.0, on_change = "Self::set_fov"))]
+//!     fov: f32,
+//!     #[imgui(drag(on_change = "update_view"))]
+//!     position: [f32; 3],
+//!     #[imgui(button(label = "Reset", on_click = "Self::reset"))]
+//!     _reset: (),
+//!     tan_half_fov: f32,
+//! }
+//!
+//! impl Camera {
+//!     fn set_fov(&mut self, fov: f32) {
+//!         self.tan_half_fov = (fov.to_radians() / 2.0).tan();
+//!     }
+//!
+//!     fn update_view(&mut self) {
+//!         // ...
+//!     }
+//!
+//!     fn reset(&mut self) {
+//!         self.position = [0.0; 3];
+//!     }
+//! }
+//! ```
+
+/// Trait implemented by the functions of the `on_change` and `on_click` params.
+///
+/// `Args` is one of the marker types [`Ext`] or [`ExtEvent`], which allow the
+/// trait to be implemented for both kinds of functions.
+pub trait Callback<T, E, Args> {
+    /// Calls the function. The event is computed from `ext` only when the
+    /// function takes it.
+    fn call<F: FnOnce(&T) -> E>(self, ext: &mut T, event: F);
+}
+
+/// Marker for callbacks taking `&mut T` only.
+pub struct Ext;
+
+/// Marker for callbacks taking `&mut T` and the event.
+pub struct ExtEvent;
+
+impl<T, E, C: FnOnce(&mut T)> Callback<T, E, Ext> for C {
+    #[inline]
+    fn call<F: FnOnce(&T) -> E>(self, ext: &mut T, _: F) {
+        self(ext)
+    }
+}
+
+impl<T, E, C: FnOnce(&mut T, E)> Callback<T, E, ExtEvent> for C {
+    #[inline]
+    fn call<F: FnOnce(&T) -> E>(self, ext: &mut T, event: F) {
+        let event = event(ext);
+        self(ext, event)
+    }
+}
@@ -35,6 +35,15 @@ pub struct CheckboxParams<'a> {
 /// Trait for types that can be represented with a checkbox.
 pub trait Checkbox {
     fn build(ui: &Ui, elem: &mut Self, params: CheckboxParams) -> bool;
+
+    /// Renders the checkbox greyed out, without modifying the value.
+    fn build_readonly(ui: &Ui, elem: &Self, params: CheckboxParams)
+    where
+        Self: Clone,
+    {
+        let mut elem = elem.clone();
+        crate::readonly(ui, || Self::build(ui, &mut elem, params));
+    }
 }
 
 impl<C: Checkbox> Checkbox for Option<C> {
@@ -111,14 +111,40 @@ pub struct ColorPickerParams<'a> {
 
 pub trait ColorButton {
     fn build(ui: &Ui, elem: Self, params: ColorButtonParams) -> bool;
+
+    /// Renders the color button greyed out.
+    fn build_readonly(ui: &Ui, elem: Self, params: ColorButtonParams)
+    where
+        Self: Sized,
+    {
+        crate::readonly(ui, || Self::build(ui, elem, params));
+    }
 }
 
 pub trait ColorEdit {
     fn build(ui: &Ui, elem: Self, params: ColorEditParams) -> bool;
+
+    /// Renders the color edit greyed out. `elem` should be a copy of the
+    /// value, as the changes are not discarded.
+    fn build_readonly(ui: &Ui, elem: Self, params: ColorEditParams)
+    where
+        Self: Sized,
+    {
+        crate::readonly(ui, || Self::build(ui, elem, params));
+    }
 }
 
 pub trait ColorPicker {
     fn build(ui: &Ui, elem: Self, params: ColorPickerParams) -> bool;
+
+    /// Renders the color picker greyed out. `elem` should be a copy of the
+    /// value, as the changes are not discarded.
+    fn build_readonly(ui: &Ui, elem: Self, params: ColorPickerParams)
+    where
+        Self: Sized,
+    {
+        crate::readonly(ui, || Self::build(ui, elem, params));
+    }
 }
 
 impl<C: Into<[f32; 4]>> ColorButton for C {
@@ -0,0 +1,87 @@
+//!
+//! Context menu opened by right clicking a widget. It is enabled for all the
+//! widgets of a type using the `context_menu` annotation on the struct:
+//!
+//! * `#[imgui(context_menu)]` adds all the builtin items.
+//! * `#[imgui(context_menu(...))]` adds the given items only.
+//!
+//! The menu is added to the widgets that render the value of a field
+//! (`checkbox`, `input`, `slider`, `drag`, and `color(edit)` / `color(picker)`),
+//! as long as they don't use a `map` function.
+//!
+//! A single widget can also define its own menu (with the same syntax), which
+//! replaces the one of the struct: `#[imgui(input(context_menu(reset)))]`.
+//!
+//! ## Items
+//!
+//! * `reset` sets the field to its default value. The value is taken from the
+//!   `default` param of the annotation, or from the [`Default`] implementation
+//!   of the struct when missing.
+//! * `copy` copies the value of the field into the clipboard.
+//! * `paste` parses the contents of the clipboard into the field. Text that
+//!   can't be parsed is ignored.
+//! * `item(label = "...", call = "...")` a user defined item. `call` is a path
+//!   to a function taking `&mut Self`, called when the item is clicked.
+//!
+//! `copy` requires the type of the field to implement [`Display`], and `paste`
+//! to implement [`FromStr`].
+//!
+//! ## Optional params
+//!
+//! * `default` value restored by the `reset` item. Either a literal
+//!   (`default = 0.5`), or a path to a function returning the value
+//!   (`default = "my_default"`). It is an error to set it on a widget without
+//!   a `reset` item.
+//!
+//! ## Example
+//!
+//! ```
+//! #[derive(imgui_ext::Gui, Default)]
+//! #[imgui(context_menu(reset, copy, paste, item(label = "Randomize", call = "Self::randomize")))]
+//! struct Light {
+//!     #[imgui(slider(min = 0.0, max = 10.0, default = 1.0))]
+//!     intensity: f32,
+//!     #[imgui(drag(default = "samples"))]
+//!     samples: u32,
+//! }
+//!
+//! impl Light {
+//!     fn randomize(&mut self) {
+//!         self.intensity = 4.0;
+//!     }
+//! }
+//!
+//! fn samples() -> u32 {
+//!     16
+//! }
+//! ```
+use imgui::{ImStr, ImString, MenuItem, MouseButton, Ui};
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Renders the context menu of the last widget, opened by right clicking it.
+/// `id` identifies the menu within the window.
+pub fn build<F: FnOnce()>(ui: &Ui, id: &ImStr, f: F) {
+    if ui.is_item_hovered() && ui.is_mouse_clicked(MouseButton::Right) {
+        ui.open_popup(id);
+    }
+    ui.popup(id, f);
+}
+
+/// Context menu item. Returns `true` when clicked.
+pub fn item(ui: &Ui, label: &ImStr) -> bool {
+    MenuItem::new(label).build(ui)
+}
+
+/// Copies a value into the clipboard.
+pub fn set_clipboard<T: Display>(ui: &Ui, value: &T) {
+    ui.set_clipboard_text(&ImString::new(value.to_string()));
+}
+
+/// Parses the contents of the clipboard. Returns `None` when it's empty or it
+/// can't be parsed.
+pub fn clipboard<T: FromStr>(ui: &Ui) -> Option<T> {
+    ui.clipboard_text()
+        .and_then(|text| text.to_str().trim().parse().ok())
+}
@@ -1,8 +1,8 @@
 //!
 //! ## Optional params
 //!
-//! * `min` maximum value
-//! * `max` minmum value
+//! * `min` minimum value
+//! * `max` maximum value
 //! * `speed`
 //! * `power`
 //! * `format` (format string in `printf` format)
@@ -24,6 +24,15 @@ pub struct DragParams<'a, T> {
 
 pub trait Drag<T> {
     fn build(ui: &imgui::Ui, elem: &mut Self, params: DragParams<T>) -> bool;
+
+    /// Renders the drag widget greyed out, without modifying the value.
+    fn build_readonly(ui: &Ui, elem: &Self, params: DragParams<T>)
+    where
+        Self: Clone,
+    {
+        let mut elem = elem.clone();
+        crate::readonly(ui, || Self::build(ui, &mut elem, params));
+    }
 }
 
 impl<T, D: Drag<T>> Drag<T> for Option<D> {
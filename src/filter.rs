@@ -0,0 +1,265 @@
+//!
+//! Search box to filter the fields of large derived UIs.
+//!
+//! [`UiExt::draw_gui_filtered`] renders a [`Filter`] above the UI of a value,
+//! and hides every field whose label and path (the names of the nested
+//! fields joined with dots, such as `"light.color"`) don't contain the text
+//! of the filter (ignoring ASCII case).
+//!
+//! * Containers with matching fields are kept, and `tree` nodes are opened.
+//! * All the fields of a matching container are kept.
+//! * The matched part of the labels is highlighted.
+//!
+//! The fields are matched using the [`Visit`] implementation of the type.
+//!
+//! # Example
+//!
+//! ```
+//! use imgui_ext::filter::Filter;
+//! use imgui_ext::UiExt;
+//!
+//! #[derive(imgui_ext::Gui)]
+//! struct Settings {
+//!     #[imgui(nested)]
+//!     shadows: Shadows,
+//!     #[imgui(slider(min = 0.0, max = 4.0))]
+//!     exposure: f32,
+//! }
+//!
+//! #[derive(imgui_ext::Gui)]
+//! struct Shadows {
+//!     #[imgui(drag(speed = 0.001))]
+//!     bias: f32,
+//!     #[imgui(checkbox(label = "Soft shadows"))]
+//!     soft: bool,
+//! }
+//!
+//! # fn frame(ui: &imgui::Ui, settings: &mut Settings, filter: &mut Filter) {
+//! // typing "soft" only shows the `shadows.soft` checkbox
+//! ui.draw_gui_filtered(settings, filter);
+//! # }
+//! ```
+//!
+//! [`UiExt::draw_gui_filtered`]: ../trait.UiExt.html#tymethod.draw_gui_filtered
+//! [`Filter`]: ./struct.Filter.html
+//! [`Visit`]: ../visit/trait.Visit.html
+use imgui::{im_str, sys, ImString, Ui};
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use crate::visit::{ValueMut, Visit, Visitor, Widget};
+
+/// Color of the highlighted part of the labels.
+pub const COLOR: [f32; 4] = [1.0, 0.8, 0.0, 0.35];
+
+/// Text of the search box.
+pub struct Filter {
+    text: ImString,
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Filter {
+    pub fn new() -> Self {
+        Self {
+            text: ImString::with_capacity(64),
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        self.text.to_str()
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.text.clear();
+        self.text.push_str(text);
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+    }
+
+    /// The filter is empty (nothing is hidden).
+    pub fn is_empty(&self) -> bool {
+        self.text().trim().is_empty()
+    }
+
+    /// Paths of the fields of `T` kept by the filter (including the
+    /// containers of the matching fields).
+    pub fn matches<T: Visit>(&self) -> HashSet<String> {
+        let mut matcher = Matcher {
+            filter: self.text().trim().to_ascii_lowercase(),
+            stack: Vec::new(),
+            visible: HashSet::new(),
+        };
+        T::describe(&mut matcher);
+        matcher.visible
+    }
+
+    /// Renders the search box. Returns `true` when the text is edited.
+    pub fn draw(&mut self, ui: &Ui) -> bool {
+        let id = ui.push_id("imgui_ext_filter");
+        let mut edited = ui.input_text(im_str!("Search"), &mut self.text).build();
+        ui.same_line(0.0);
+        if ui.button(im_str!("Clear"), [0.0, 0.0]) {
+            self.text.clear();
+            edited = true;
+        }
+        id.pop(ui);
+        edited
+    }
+}
+
+// Collects the paths of the matching fields (and of their containers).
+struct Matcher {
+    filter: String,
+    // containers being visited: the field (for `nested` and `menu`, which
+    // contain the fields of another type) and whether the container matched
+    stack: Vec<(Option<String>, bool)>,
+    visible: HashSet<String>,
+}
+
+impl Matcher {
+    fn path(&self, field: &str) -> String {
+        let mut path: Vec<&str> = self
+            .stack
+            .iter()
+            .filter_map(|(field, _)| field.as_ref().map(String::as_str))
+            .collect();
+        path.push(field);
+        path.join(".")
+    }
+
+    // Returns whether the widget matched, keeping its path if it did.
+    fn visit(&mut self, widget: &Widget) -> bool {
+        let path = self.path(widget.field);
+        let matched = self.stack.iter().any(|(_, matched)| *matched)
+            || path.to_ascii_lowercase().contains(&self.filter)
+            || widget.label.to_ascii_lowercase().contains(&self.filter);
+        if matched {
+            let mut prefix = String::new();
+            for field in path.split('.') {
+                if !prefix.is_empty() {
+                    prefix.push('.');
+                }
+                prefix.push_str(field);
+                self.visible.insert(prefix.clone());
+            }
+        }
+        matched
+    }
+}
+
+impl Visitor for Matcher {
+    fn widget(&mut self, widget: &Widget, _: ValueMut) {
+        self.visit(widget);
+    }
+
+    fn begin(&mut self, widget: &Widget) {
+        let matched = self.visit(widget);
+        let field = match widget.kind {
+            "nested" | "menu" => Some(widget.field.to_string()),
+            _ => None,
+        };
+        self.stack.push((field, matched));
+    }
+
+    fn end(&mut self, _: &Widget) {
+        self.stack.pop();
+    }
+}
+
+thread_local! {
+    // State of the filter (if `with_filter` is running).
+    static FILTER: RefCell<Option<State>> = RefCell::new(None);
+}
+
+struct State {
+    // lowercase text of the filter
+    text: String,
+    visible: HashSet<String>,
+    // path of the field being drawn
+    path: Vec<&'static str>,
+}
+
+/// Hides the fields of `T` that don't match the filter while running `f`.
+pub fn with_filter<T: Visit, R, F: FnOnce() -> R>(filter: &Filter, f: F) -> R {
+    if filter.is_empty() {
+        return f();
+    }
+    let state = State {
+        text: filter.text().trim().to_ascii_lowercase(),
+        visible: filter.matches::<T>(),
+        path: Vec::new(),
+    };
+    let prev = FILTER.with(|s| s.borrow_mut().replace(state));
+    let ret = f();
+    FILTER.with(|s| *s.borrow_mut() = prev);
+    ret
+}
+
+/// Called before the widgets of a field. Returns whether they are drawn.
+pub(crate) fn begin(field: &'static str) -> bool {
+    FILTER.with(|s| match s.borrow_mut().as_mut() {
+        Some(s) => {
+            s.path.push(field);
+            s.visible.contains(&s.path.join("."))
+        }
+        None => true,
+    })
+}
+
+/// Called after the widgets of a field.
+pub(crate) fn end() {
+    FILTER.with(|s| {
+        if let Some(s) = s.borrow_mut().as_mut() {
+            s.path.pop();
+        }
+    });
+}
+
+/// Whether a filter is running (used to open the `tree` nodes).
+pub(crate) fn filtering() -> bool {
+    FILTER.with(|s| s.borrow().is_some())
+}
+
+/// Highlights the matched part of the label of the last widget.
+pub(crate) fn item(ui: &Ui, kind: &str, label: &str) {
+    FILTER.with(|s| {
+        if let Some(s) = s.borrow().as_ref() {
+            // the part after `##` is not displayed
+            let label = label.split("##").next().unwrap_or_default();
+            if let Some(start) = label.to_ascii_lowercase().find(&s.text) {
+                let end = start + s.text.len();
+                let (min, max) = unsafe { (sys::igGetItemRectMin(), sys::igGetItemRectMax()) };
+                let [width, height] = text_size(label);
+                let x = match kind {
+                    "button" => (min.x + max.x - width) / 2.0,
+                    "menu_item" => min.x,
+                    _ => max.x - width,
+                };
+                let y = (min.y + max.y - height) / 2.0;
+                let x0 = x + text_size(&label[..start])[0];
+                let x1 = x + text_size(&label[..end])[0];
+                ui.get_window_draw_list()
+                    .add_rect([x0, y], [x1, y + height], COLOR)
+                    .filled(true)
+                    .build();
+            }
+        }
+    });
+}
+
+fn text_size(text: &str) -> [f32; 2] {
+    if text.is_empty() {
+        return [0.0, 0.0];
+    }
+    let start = text.as_ptr();
+    let size = unsafe { sys::igCalcTextSize(start as _, start.add(text.len()) as _, false, -1.0) };
+    [size.x, size.y]
+}
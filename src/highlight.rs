@@ -0,0 +1,100 @@
+//!
+//! Highlights the widgets of some of the fields of a derived type.
+//!
+//! Fields are identified by their path from the type being drawn: the name of
+//! the field, or the names of the nested fields joined with dots
+//! (`"light.color"`). The widgets of a highlighted field are drawn with a
+//! different frame and button color.
+//!
+//! # Example
+//!
+//! ```
+//! use imgui_ext::highlight;
+//! use imgui_ext::UiExt;
+//!
+//! #[derive(imgui_ext::Gui)]
+//! struct Example {
+//!     #[imgui(slider(min = 0.0, max = 1.0))]
+//!     x: f32,
+//!     #[imgui(nested)]
+//!     nested: Nested,
+//! }
+//!
+//! #[derive(imgui_ext::Gui)]
+//! struct Nested {
+//!     #[imgui(checkbox)]
+//!     y: bool,
+//! }
+//!
+//! # fn frame(ui: &imgui::Ui, example: &mut Example) {
+//! let fields = ["x".to_string(), "nested.y".to_string()];
+//! highlight::with_highlight(&fields, || ui.draw_gui(example));
+//! # }
+//! ```
+use imgui::{ColorStackToken, StyleColor, Ui};
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+/// Color of the frames and buttons of the highlighted widgets.
+pub const COLOR: [f32; 4] = [0.7, 0.5, 0.1, 0.8];
+
+const COLORS: &[(StyleColor, [f32; 4])] = &[
+    (StyleColor::FrameBg, COLOR),
+    (StyleColor::FrameBgHovered, COLOR),
+    (StyleColor::Button, COLOR),
+    (StyleColor::Header, COLOR),
+];
+
+thread_local! {
+    // Highlighted fields (if `with_highlight` is running).
+    static HIGHLIGHT: RefCell<Option<Highlight>> = RefCell::new(None);
+}
+
+struct Highlight {
+    fields: HashSet<String>,
+    // path of the field being drawn, with the pushed colors
+    path: Vec<(&'static str, Option<ColorStackToken>)>,
+}
+
+/// Highlights the widgets of the given fields while running `f`.
+pub fn with_highlight<R, F: FnOnce() -> R>(fields: &[String], f: F) -> R {
+    if fields.is_empty() {
+        return f();
+    }
+    let highlight = Highlight {
+        fields: fields.iter().cloned().collect(),
+        path: Vec::new(),
+    };
+    let prev = HIGHLIGHT.with(|h| h.borrow_mut().replace(highlight));
+    let ret = f();
+    HIGHLIGHT.with(|h| *h.borrow_mut() = prev);
+    ret
+}
+
+/// Called before the widgets of a field.
+pub(crate) fn begin(ui: &Ui, field: &'static str) {
+    HIGHLIGHT.with(|h| {
+        if let Some(h) = h.borrow_mut().as_mut() {
+            let mut path: Vec<_> = h.path.iter().map(|(field, _)| *field).collect();
+            path.push(field);
+            let colors = if h.fields.contains(&path.join(".")) {
+                Some(ui.push_style_colors(COLORS))
+            } else {
+                None
+            };
+            h.path.push((field, colors));
+        }
+    });
+}
+
+/// Called after the widgets of a field.
+pub(crate) fn end(ui: &Ui) {
+    HIGHLIGHT.with(|h| {
+        if let Some(h) = h.borrow_mut().as_mut() {
+            if let Some((_, Some(colors))) = h.path.pop() {
+                colors.pop(ui);
+            }
+        }
+    });
+}
@@ -0,0 +1,288 @@
+//!
+//! Keeps a value in sync with a file on disk (requires the `hot_reload`
+//! feature).
+//!
+//! [`HotReload`] polls the modification time of the file. When the file
+//! changes, only the fields that were modified on disk are merged into the
+//! live value, so the edits made with the UI to other fields are kept. The
+//! widgets of the merged fields are highlighted for a while (see the
+//! [`highlight`] module).
+//!
+//! Optionally, the edits made with the UI are written back to the file (at
+//! most once per poll, and not while a widget is being edited).
+//!
+//! The file uses the same formats as the [`presets`] (JSON by default).
+//!
+//! # Example
+//!
+//! ```no_run
+//! use imgui_ext::hot_reload::HotReload;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(imgui_ext::Gui, Serialize, Deserialize)]
+//! struct Config {
+//!     #[imgui(slider(min = 0.0, max = 10.0))]
+//!     speed: f32,
+//! }
+//!
+//! # fn frame(ui: &imgui::Ui, hot: &mut HotReload, config: &mut Config) {
+//! let events = hot.draw_gui(ui, config);
+//!
+//! for field in events.changed() {
+//!     println!("{} changed on disk", field);
+//! }
+//! # }
+//!
+//! let mut hot = HotReload::new("config.json").write_back(true);
+//! ```
+//!
+//! [`HotReload`]: ./struct.HotReload.html
+//! [`highlight`]: ../highlight/index.html
+//! [`presets`]: ../presets/index.html
+use imgui::Ui;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::highlight;
+use crate::presets::{Error, Format, Json};
+use crate::Gui;
+
+/// Events returned by `HotReload::draw_gui`.
+pub struct ReloadEvents<E> {
+    /// Events of the value.
+    pub events: E,
+    changed: Vec<String>,
+    written: bool,
+}
+
+impl<E> ReloadEvents<E> {
+    /// Paths of the fields merged from disk during this frame (empty if the
+    /// file didn't change).
+    pub fn changed(&self) -> &[String] {
+        &self.changed
+    }
+
+    /// The value was written back to the file during this frame.
+    pub fn written(&self) -> bool {
+        self.written
+    }
+}
+
+/// Watches a file containing a serialized value.
+pub struct HotReload<F = Json> {
+    path: PathBuf,
+    format: F,
+    interval: Duration,
+    highlight: Duration,
+    write_back: bool,
+    last_poll: Option<Instant>,
+    modified: Option<SystemTime>,
+    // contents of the file when it was last read or written
+    base: Option<Value>,
+    changed: Vec<String>,
+    changed_at: Option<Instant>,
+    error: Option<String>,
+}
+
+impl HotReload<Json> {
+    /// Watches a JSON file.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self::with_format(path, Json)
+    }
+}
+
+impl<F: Format> HotReload<F> {
+    pub fn with_format<P: Into<PathBuf>>(path: P, format: F) -> Self {
+        Self {
+            path: path.into(),
+            format,
+            interval: Duration::from_millis(500),
+            highlight: Duration::from_secs(2),
+            write_back: false,
+            last_poll: None,
+            modified: None,
+            base: None,
+            changed: Vec::new(),
+            changed_at: None,
+            error: None,
+        }
+    }
+
+    /// Time between polls of the file (500ms by default).
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Time the merged fields are highlighted for (2s by default).
+    pub fn highlight(mut self, highlight: Duration) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Write the edits made with the UI back to the file.
+    ///
+    /// The edits are written when the file is polled (every `interval`), once
+    /// no widget is active (e.g. after a drag is released).
+    pub fn write_back(mut self, write_back: bool) -> Self {
+        self.write_back = write_back;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Last error reading or writing the file.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_ref().map(String::as_str)
+    }
+
+    /// Reads the file if it was modified since the last call, and merges the
+    /// fields that changed on disk into `value`. Returns the paths of the
+    /// merged fields.
+    ///
+    /// The first read replaces the whole value.
+    pub fn poll<T>(&mut self, value: &mut T) -> Result<Vec<String>, Error>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let modified = fs::metadata(&self.path)?.modified()?;
+        if self.modified == Some(modified) {
+            return Ok(Vec::new());
+        }
+        self.modified = Some(modified);
+
+        let text = fs::read_to_string(&self.path)?;
+        let disk: T = self.format.deserialize(&text)?;
+        let disk = to_value(&disk)?;
+
+        let mut changed = Vec::new();
+        match self.base.take() {
+            None => *value = from_value(disk.clone())?,
+            Some(base) => {
+                let mut live = to_value(value)?;
+                merge(&mut live, &base, &disk, "", &mut changed);
+                if !changed.is_empty() {
+                    *value = from_value(live)?;
+                }
+            }
+        }
+        self.base = Some(disk);
+        Ok(changed)
+    }
+
+    /// Writes `value` to the file.
+    pub fn write<T: Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        let text = self.format.serialize(value)?;
+        fs::write(&self.path, text)?;
+        // so the write is not read back as a change
+        self.modified = Some(fs::metadata(&self.path)?.modified()?);
+        self.base = Some(to_value(value)?);
+        Ok(())
+    }
+
+    /// Polls the file (every `interval`) and renders the UI of `value`,
+    /// highlighting the fields merged from disk.
+    pub fn draw_gui<T>(&mut self, ui: &Ui, value: &mut T) -> ReloadEvents<T::Events>
+    where
+        T: Gui + Serialize + DeserializeOwned,
+    {
+        let now = Instant::now();
+        let mut changed = Vec::new();
+
+        let poll = match self.last_poll {
+            Some(last) => now.duration_since(last) >= self.interval,
+            None => true,
+        };
+        if poll {
+            self.last_poll = Some(now);
+            match self.poll(value) {
+                Ok(fields) => {
+                    if !fields.is_empty() {
+                        self.changed = fields.clone();
+                        self.changed_at = Some(now);
+                    }
+                    changed = fields;
+                    self.error = None;
+                }
+                Err(err) => self.error = Some(err.to_string()),
+            }
+        }
+
+        let highlighted: &[String] = match self.changed_at {
+            Some(at) if now.duration_since(at) < self.highlight => &self.changed[..],
+            _ => &[],
+        };
+
+        let events = highlight::with_highlight(highlighted, || T::draw_gui(ui, value));
+
+        if let Some(error) = self.error.as_ref() {
+            ui.text_colored([1.0, 0.0, 0.0, 1.0], error);
+        }
+
+        // the value is serialized once per poll, and not while a widget is
+        // being edited, so a drag doesn't rewrite the file every frame
+        let mut written = false;
+        if self.write_back && poll && !ui.is_any_item_active() {
+            let edited = match (to_value(value), self.base.as_ref()) {
+                (Ok(live), Some(base)) => live != *base,
+                _ => false,
+            };
+            if edited {
+                match self.write(value) {
+                    Ok(()) => written = true,
+                    Err(err) => self.error = Some(err.to_string()),
+                }
+            }
+        }
+
+        ReloadEvents {
+            events,
+            changed,
+            written,
+        }
+    }
+}
+
+fn to_value<T: Serialize>(value: &T) -> Result<Value, Error> {
+    serde_json::to_value(value).map_err(|err| Error::Format(err.to_string()))
+}
+
+fn from_value<T: DeserializeOwned>(value: Value) -> Result<T, Error> {
+    serde_json::from_value(value).map_err(|err| Error::Format(err.to_string()))
+}
+
+// Copies into `live` the fields of `disk` that are different in `base`, and
+// pushes their paths into `changed`. Objects are merged field by field.
+fn merge(live: &mut Value, base: &Value, disk: &Value, path: &str, changed: &mut Vec<String>) {
+    match (live, base, disk) {
+        (Value::Object(live), Value::Object(base), Value::Object(disk)) => {
+            for (name, disk) in disk.iter() {
+                let path = if path.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", path, name)
+                };
+                match (live.get_mut(name), base.get(name)) {
+                    (Some(live), Some(base)) => merge(live, base, disk, &path, changed),
+                    _ => {
+                        live.insert(name.clone(), disk.clone());
+                        changed.push(path);
+                    }
+                }
+            }
+        }
+        (live, base, disk) => {
+            if base != disk {
+                *live = disk.clone();
+                changed.push(path.to_string());
+            }
+        }
+    }
+}
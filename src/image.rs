@@ -67,6 +67,14 @@ pub struct ImageParams {
 
 pub trait Image {
     fn build(ui: &Ui, elem: Self, params: ImageParams);
+
+    /// Renders the image greyed out.
+    fn build_readonly(ui: &Ui, elem: Self, params: ImageParams)
+    where
+        Self: Sized,
+    {
+        crate::readonly(ui, || Self::build(ui, elem, params));
+    }
 }
 
 impl<T> Image for T
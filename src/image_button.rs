@@ -30,6 +30,14 @@ pub struct ImageButtonParams {
 
 pub trait ImageButton {
     fn build(ui: &Ui, elem: Self, params: ImageButtonParams);
+
+    /// Renders the image button greyed out.
+    fn build_readonly(ui: &Ui, elem: Self, params: ImageButtonParams)
+    where
+        Self: Sized,
+    {
+        crate::readonly(ui, || Self::build(ui, elem, params));
+    }
 }
 
 impl<T> ImageButton for T
@@ -93,6 +93,15 @@ pub struct InputParams<'a, T> {
 
 pub trait Input<T> {
     fn build(ui: &Ui, elem: &mut Self, params: InputParams<T>) -> bool;
+
+    /// Renders the input greyed out, without modifying the value.
+    fn build_readonly(ui: &Ui, elem: &Self, params: InputParams<T>)
+    where
+        Self: Clone,
+    {
+        let mut elem = elem.clone();
+        crate::readonly(ui, || Self::build(ui, &mut elem, params));
+    }
 }
 
 impl<T, I: Input<T>> Input<T> for Box<I> {
@@ -81,9 +81,10 @@
 //! [repo]: https://github.com/germangb/imgui-ext
 #![deny(warnings)]
 
-use imgui::Ui;
+use imgui::{sys, Ui};
 
-pub use imgui_ext_derive::Gui;
+pub use imgui_ext_derive::{actions, Gui};
+pub use visit::Visit;
 
 include!("macros.rs");
 
@@ -206,22 +207,103 @@ pub mod tree {
     //!
     //! ![](https://i.imgur.com/Rn2RJJG.png)
 }
+/// `child(...)` docs.
+pub mod child {
+    //!
+    //! Renders widgets inside of a child window, which has its own ID and
+    //! scrollbar. Useful when a large nested UI would otherwise overflow the
+    //! parent window.
+    //!
+    //! Input events from the widgets inside of the child window are reported on
+    //! the events type of the parent, as if the annotations weren't nested.
+    //!
+    //! # Params
+    //!
+    //! - `content(...)` widgets to render inside of the child window.
+    //!
+    //! # Optional params
+    //!
+    //! - `label = ".."` child window ID. Defaults to the field name.
+    //! - `size = ".."` path to a function returning the size of the child
+    //!   window. Zero components take the remaining space.
+    //! - `border = bool` draw a border around the child window.
+    //! - `flags = ".."` path to a function returning [`ImGuiWindowFlags`].
+    //!
+    //! [`ImGuiWindowFlags`]: https://docs.rs/imgui/*/imgui/struct.ImGuiWindowFlags.html
+    //!
+    //! # Example
+    //!
+    //! ```
+    //! #[derive(imgui_ext::Gui)]
+    //! pub struct Settings {
+    //!     #[imgui(child(size = "size", border = true, content(nested)))]
+    //!     lights: Lights,
+    //! }
+    //!
+    //! fn size() -> [f32; 2] {
+    //!     [0.0, 128.0]
+    //! }
+    //!
+    //! #[derive(imgui_ext::Gui)]
+    //! pub struct Lights {
+    //!     #[imgui(slider(min = 0.0, max = 1.0))]
+    //!     ambient: [f32; 3],
+    //!     #[imgui(slider(min = 0.0, max = 1.0))]
+    //!     diffuse: [f32; 3],
+    //! }
+    //! ```
+}
+/// Highlighting of the fields that differ from a baseline value.
+pub mod baseline;
+/// `on_change` & `on_click` docs.
+pub mod callback;
 /// `checkbox(...)` docs.
 pub mod checkbox;
 /// `color(...)` docs.
 pub mod color;
+/// `context_menu(...)` docs.
+pub mod context_menu;
 /// `drag(...)` docs.
 pub mod drag;
+/// Search box to filter the fields of derived UIs.
+pub mod filter;
+/// Highlighting of fields.
+pub mod highlight;
+/// Keeps a value in sync with a file on disk.
+#[cfg(feature = "hot_reload")]
+pub mod hot_reload;
 /// `image(...)` docs.
 pub mod image;
 /// `image_button(...)` docs.
 pub mod image_button;
 /// `input(...)` docs.
 pub mod input;
+/// `menu(...)` & `menu_item(...)` docs.
+pub mod menu;
+/// Editing of many values at once.
+pub mod multi;
+/// Named presets stored in a local directory.
+#[cfg(feature = "presets")]
+pub mod presets;
 /// `progress(...)` docs.
 pub mod progress;
+/// Registry of tweakable values.
+pub mod registry;
+/// Inspection of values over a local socket.
+#[cfg(feature = "remote")]
+pub mod remote;
+/// Serializable description of derived UIs.
+#[cfg(feature = "schema")]
+pub mod schema;
 /// `slider(...)` docs.
 pub mod slider;
+/// `clamp(...)`, `validate` & `on_change` docs.
+pub mod validate;
+/// Headless harness to test derived UIs.
+#[cfg(feature = "testing")]
+pub mod testing;
+/// `Visit` trait docs.
+pub mod visit;
 /// `text(...)` & `text_wrap(...)` docs.
 pub mod text {
     //!
@@ -264,6 +346,150 @@ pub mod misc {
     //! * `#[imgui(separator)]` inserts a separator
     //! * `#[imgui(new_line)]` inserts an empty line
 }
+/// `tooltip` & `help` docs.
+pub mod tooltip {
+    //!
+    //! Params accepted by every annotation, used to explain what a widget does.
+    //!
+    //! * `tooltip = "..."` text shown when the widget is hovered.
+    //! * `help = "..."` places a `(?)` marker next to the widget, which shows
+    //!   the text when hovered.
+    //!
+    //! When written without a value (`tooltip` or `help`), the text is taken
+    //! from the doc comment of the field.
+    //!
+    //! # Example
+    //!
+    //! ```
+    //! #[derive(imgui_ext::Gui)]
+    //! struct Example {
+    //!     #[imgui(slider(min = 0.0, max = 1.0, tooltip = "Amount of light bounced."))]
+    //!     albedo: f32,
+    //!
+    //!     /// Offset applied to the depth of the shadow map, to avoid acne.
+    //!     #[imgui(drag(speed = 0.001, help))]
+    //!     shadow_bias: f32,
+    //! }
+    //! ```
+    //!
+    //! # Doc comments
+    //!
+    //! Structs that are documented already can show the doc comments of all
+    //! of their annotated fields, without repeating the text on every
+    //! annotation, using the `docs` attribute on the struct:
+    //!
+    //! * `#[imgui(docs)]` or `#[imgui(docs = "tooltip")]` shows the doc comment
+    //!   when a widget is hovered (unless it defines a `tooltip` already).
+    //! * `#[imgui(docs = "verbose")]` shows the doc comment as wrapped text,
+    //!   below the widgets of the field.
+    //!
+    //! ```
+    //! #[derive(imgui_ext::Gui)]
+    //! #[imgui(docs = "verbose")]
+    //! struct Example {
+    //!     /// Amount of light bounced.
+    //!     #[imgui(slider(min = 0.0, max = 1.0))]
+    //!     albedo: f32,
+    //!
+    //!     /// Offset applied to the depth of the shadow map, to avoid acne.
+    //!     #[imgui(drag(speed = 0.001))]
+    //!     shadow_bias: f32,
+    //! }
+    //! ```
+}
+/// `readonly` & `disabled_if` docs.
+pub mod readonly {
+    //!
+    //! Renders the widgets greyed out and disabled, without modifying the
+    //! values. Read-only widgets never report any events. Useful to show state
+    //! that can't be edited (e.g. values owned by a server).
+    //!
+    //! Any type can be rendered in read-only mode with
+    //! [`UiExt::draw_gui_readonly`](../trait.UiExt.html), which takes `&T`
+    //! instead of `&mut T`.
+    //!
+    //! The widgets render a copy of the field, so the types of the fields must
+    //! implement `Clone`.
+    //!
+    //! Individual fields can be made read-only with params accepted by every
+    //! annotation:
+    //!
+    //! * `readonly` always renders the widget in read-only mode.
+    //! * `disabled_if = "method"` renders the widget in read-only mode when
+    //!   the given method of the struct (taking `&self`) returns `true`.
+    //!
+    //! The context menu of read-only widgets is never shown.
+    //!
+    //! Types with a hand-written `Gui` implementation render nothing in
+    //! read-only mode, unless they implement `Gui::draw_gui_readonly`.
+    //!
+    //! # Example
+    //!
+    //! ```
+    //! use imgui_ext::UiExt;
+    //!
+    //! #[derive(imgui_ext::Gui)]
+    //! struct Player {
+    //!     #[imgui(checkbox(label = "Server authoritative"))]
+    //!     locked: bool,
+    //!     #[imgui(drag(disabled_if = "is_locked"))]
+    //!     position: [f32; 3],
+    //!     #[imgui(input(readonly))]
+    //!     id: u32,
+    //! }
+    //!
+    //! impl Player {
+    //!     fn is_locked(&self) -> bool {
+    //!         self.locked
+    //!     }
+    //! }
+    //!
+    //! # fn draw(ui: &imgui::Ui) {
+    //! let player = Player { locked: true, position: [0.0; 3], id: 42 };
+    //!
+    //! // nothing can be edited
+    //! ui.draw_gui_readonly(&player);
+    //! # }
+    //! ```
+}
+/// `visible_if` & `enabled_if` docs.
+pub mod conditions {
+    //!
+    //! Params accepted by every annotation, used to show or enable widgets
+    //! depending on the state of the struct:
+    //!
+    //! * `visible_if = "..."` skips the widget unless the condition is `true`.
+    //! * `enabled_if = "..."` renders the widget in [read-only
+    //!   mode](../readonly/index.html) unless the condition is `true`.
+    //!
+    //! The condition is either the name of a `bool` field of the struct, or a
+    //! path to a function taking `&Self` and returning `bool` (such as
+    //! `Self::method`). Free functions in the same module can be written as
+    //! `self::function`.
+    //!
+    //! When a field has more than one annotation, the params only affect the
+    //! annotation they are written in.
+    //!
+    //! # Example
+    //!
+    //! ```
+    //! #[derive(imgui_ext::Gui)]
+    //! struct Light {
+    //!     #[imgui(checkbox)]
+    //!     shadows: bool,
+    //!     #[imgui(drag(speed = 0.001, visible_if = "shadows"))]
+    //!     shadow_bias: f32,
+    //!     #[imgui(slider(min = 0, max = 4, enabled_if = "Self::soft_shadows"))]
+    //!     pcf_samples: i32,
+    //! }
+    //!
+    //! impl Light {
+    //!     fn soft_shadows(&self) -> bool {
+    //!         self.shadows && self.shadow_bias > 0.0
+    //!     }
+    //! }
+    //! ```
+}
 /// `display(...)` docs.
 pub mod display {
     //!
@@ -356,6 +582,65 @@ pub mod nested {
     //! }
     //! ```
 }
+/// `popup(...)` & `modal(...)` docs.
+pub mod popup {
+    //!
+    //! Popup windows, opened by the input event of another annotation (usually
+    //! a `button`).
+    //!
+    //! # Variants
+    //!
+    //! - `popup(...)` regular popup, closed when clicking outside of it.
+    //! - `modal(...)` modal popup, which blocks the rest of the UI until closed.
+    //!
+    //! # Params
+    //!
+    //! - `open = ".."` name of the input event that opens the popup.
+    //!
+    //! # Optional params
+    //!
+    //! - `label = ".."` popup title & ID. Defaults to the field name.
+    //! - `content(...)` widgets rendered inside of the popup.
+    //! - `ok = ".."` label of the OK button.
+    //! - `cancel = ".."` label of the Cancel button.
+    //! - `catch = ".."` prefix of the button input events. Defaults to the field
+    //!   name.
+    //!
+    //! Both buttons close the popup. A click on them is reported by the
+    //! `<catch>_ok` and `<catch>_cancel` input events. Modals without `ok` and
+    //! `cancel` params get a pair of `"OK"` and `"Cancel"` buttons.
+    //!
+    //! # Example
+    //!
+    //! ```
+    //! use imgui_ext::UiExt;
+    //!
+    //! #[derive(imgui_ext::Gui)]
+    //! struct Scene {
+    //!     #[imgui(
+    //!         display(label = "Entities"),
+    //!         button(label = "Clear", catch = "clear"),
+    //!         modal(
+    //!             label = "Clear scene?",
+    //!             open = "clear",
+    //!             content(text("All the entities will be removed.")),
+    //!             ok = "Clear",
+    //!             cancel = "Cancel",
+    //!             catch = "confirm"
+    //!         )
+    //!     )]
+    //!     entities: usize,
+    //! }
+    //!
+    //! # fn draw(ui: &imgui::Ui) {
+    //! let mut scene = Scene { entities: 42 };
+    //!
+    //! if ui.draw_gui(&mut scene).confirm_ok() {
+    //!     scene.entities = 0;
+    //! }
+    //! # }
+    //! ```
+}
 /// `button(...)` docs.
 pub mod button {
     //!
@@ -406,6 +691,105 @@ pub mod button {
     //! ![][image]
     //!
     //! [image]: https://i.imgur.com/PpOcZK8.png
+    //!
+    //! # Actions
+    //!
+    //! Buttons that call methods of the struct can be added with the `actions`
+    //! attribute on the struct. The buttons are placed in a single line, after
+    //! the fields:
+    //!
+    //! - `method = "Label"` calls `method` (taking `&mut self`) when clicked.
+    //! - `method(label = "...", confirm = "...")` asks for confirmation in a
+    //!   modal popup with the `confirm` text, before calling the method.
+    //! - `method` uses the method name as the label.
+    //!
+    //! The clicks are also reported on the returned type, with the name of the
+    //! method (only after the action is confirmed).
+    //!
+    //! ```
+    //! use imgui_ext::UiExt;
+    //!
+    //! #[derive(imgui_ext::Gui)]
+    //! #[imgui(actions(
+    //!     randomize = "Randomize",
+    //!     reset(label = "Reset", confirm = "Discard all the changes?")
+    //! ))]
+    //! struct Particles {
+    //!     #[imgui(slider(min = 0, max = 1000))]
+    //!     count: i32,
+    //! }
+    //!
+    //! impl Particles {
+    //!     fn randomize(&mut self) {
+    //!         self.count = 42;
+    //!     }
+    //!
+    //!     fn reset(&mut self) {
+    //!         self.count = 0;
+    //!     }
+    //! }
+    //!
+    //! # fn draw(ui: &imgui::Ui) {
+    //! let mut particles = Particles { count: 100 };
+    //!
+    //! if ui.draw_gui(&mut particles).reset() {
+    //!     println!("particles reset");
+    //! }
+    //! # }
+    //! ```
+    //!
+    //! ## Impl blocks
+    //!
+    //! Alternatively, the `#[imgui_ext::actions]` attribute can be placed on an
+    //! `impl` block. Each method annotated with `#[imgui(button)]` (or
+    //! `#[imgui(button(label = "...", confirm = "..."))]`) gets a button. The
+    //! methods must take `&mut self`, and the rest of the arguments get an input
+    //! widget (a checkbox for `bool`), so they can be called from the UI. The
+    //! values of the arguments are kept between frames in a field of the struct,
+    //! of type `imgui_ext::ActionArgs<Self>`, given by the `args` param of the
+    //! attribute (`#[imgui_ext::actions(args = "field")]`). The param is only
+    //! needed when some method takes arguments, and their types must implement
+    //! `Default` and `Clone`.
+    //!
+    //! The buttons are drawn with `UiExt::draw_actions`, or after the fields of
+    //! the struct when it is annotated with `#[imgui(actions)]`. In that case, the
+    //! clicks are reported under `actions()`.
+    //!
+    //! ```
+    //! use imgui_ext::UiExt;
+    //!
+    //! #[derive(imgui_ext::Gui)]
+    //! #[imgui(actions)]
+    //! struct Particles {
+    //!     #[imgui(slider(min = 0, max = 1000))]
+    //!     count: i32,
+    //!     args: imgui_ext::ActionArgs<Self>,
+    //! }
+    //!
+    //! #[imgui_ext::actions(args = "args")]
+    //! impl Particles {
+    //!     #[imgui(button(label = "Spawn"))]
+    //!     fn spawn(&mut self, count: i32) {
+    //!         self.count += count;
+    //!     }
+    //!
+    //!     #[imgui(button(confirm = "Remove all the particles?"))]
+    //!     fn clear(&mut self) {
+    //!         self.count = 0;
+    //!     }
+    //! }
+    //!
+    //! # fn draw(ui: &imgui::Ui) {
+    //! let mut particles = Particles {
+    //!     count: 100,
+    //!     args: Default::default(),
+    //! };
+    //!
+    //! if ui.draw_gui(&mut particles).actions().clear() {
+    //!     println!("particles cleared");
+    //! }
+    //! # }
+    //! ```
 }
 /// `bullet(...)` docs.
 pub mod bullet {
@@ -439,19 +823,38 @@ pub mod bullet {
 
 /// Trait implemented by the derive macro.
 pub trait Gui {
-    type Events;
+    /// Events reported by `draw_gui`. The default value reports no events.
+    type Events: Default;
     fn draw_gui(ui: &Ui, ext: &mut Self) -> Self::Events;
+
+    /// Renders the value without modifying it. The derived implementation
+    /// renders the widgets disabled and never reports any events.
+    ///
+    /// The default implementation renders **nothing** and returns the default
+    /// events, so hand-written implementations must override it for their
+    /// widgets to show up in read-only mode (`readonly`, `disabled_if` and
+    /// `enabled_if` on a `nested` field).
+    fn draw_gui_readonly(_ui: &Ui, _ext: &Self) -> Self::Events {
+        Self::Events::default()
+    }
 }
 
 impl<T: Gui> Gui for Option<T> {
     type Events = T::Events;
 
-    // TODO remove unsafe
     fn draw_gui(ui: &Ui, ext: &mut Self) -> Self::Events {
         if let Some(ref mut ext) = ext {
             T::draw_gui(ui, ext)
         } else {
-            unsafe { std::mem::zeroed() }
+            T::Events::default()
+        }
+    }
+
+    fn draw_gui_readonly(ui: &Ui, ext: &Self) -> Self::Events {
+        if let Some(ref ext) = ext {
+            T::draw_gui_readonly(ui, ext)
+        } else {
+            T::Events::default()
         }
     }
 }
@@ -462,6 +865,170 @@ impl<T: Gui> Gui for Box<T> {
     fn draw_gui(ui: &Ui, ext: &mut Self) -> Self::Events {
         T::draw_gui(ui, ext.as_mut())
     }
+
+    #[inline]
+    fn draw_gui_readonly(ui: &Ui, ext: &Self) -> Self::Events {
+        T::draw_gui_readonly(ui, ext.as_ref())
+    }
+}
+
+/// Trait implemented by the `#[imgui_ext::actions]` attribute macro.
+pub trait Actions {
+    type Events: Default;
+    /// Values of the arguments of the methods, kept between frames in the
+    /// field given by the `args` param (see [`ActionArgs`]).
+    type Args: Default;
+    fn draw_actions(ui: &Ui, ext: &mut Self) -> Self::Events;
+    fn draw_actions_readonly(ui: &Ui, ext: &Self) -> Self::Events;
+}
+
+/// Type of the field keeping the values of the arguments of the methods of an
+/// `#[imgui_ext::actions(args = "...")]` impl block.
+pub type ActionArgs<T> = <T as Actions>::Args;
+
+/// Renders the widgets built inside of `f` greyed out and disabled.
+///
+/// Used by the `build_readonly` methods of the widget traits.
+pub fn readonly<R, F: FnOnce() -> R>(ui: &Ui, f: F) -> R {
+    // imgui 0.2 doesn't expose the disabled item flag. Hiding the mouse clicks
+    // from the widgets has the same effect: they can still be hovered (so the
+    // tooltips are shown) but never activated.
+    let io = unsafe { sys::igGetIO() };
+    let (clicked, double_clicked) = unsafe {
+        let clicked = std::mem::replace(&mut (*io).MouseClicked, [false; 5]);
+        let double_clicked = std::mem::replace(&mut (*io).MouseDoubleClicked, [false; 5]);
+        (clicked, double_clicked)
+    };
+    unsafe { sys::igPushAllowKeyboardFocus(false) };
+    let style = ui.push_style_var(imgui::StyleVar::Alpha(0.5));
+    let ret = f();
+    style.pop(ui);
+    unsafe {
+        sys::igPopAllowKeyboardFocus();
+        (*io).MouseClicked = clicked;
+        (*io).MouseDoubleClicked = double_clicked;
+    }
+    ret
+}
+
+// Called by the derived code after each widget, so the testing harness can
+// record it. The value is only formatted while recording.
+#[doc(hidden)]
+#[inline]
+pub fn __item(ui: &Ui, kind: &'static str, label: &str, _value: &dyn Fn() -> Option<String>) {
+    filter::item(ui, kind, label);
+    #[cfg(feature = "testing")]
+    testing::record(ui, kind, label, _value);
+}
+
+// Called by the derived code before the widgets of an annotation containing
+// other widgets. They are recorded as children until the scope is dropped.
+#[doc(hidden)]
+#[inline]
+pub fn __scope(_ui: &Ui, _kind: &'static str, _label: &str) -> __Scope {
+    #[cfg(feature = "testing")]
+    testing::begin(_kind, _label);
+    __Scope(())
+}
+
+// Called by the derived code before a `tree` node, to open it while the
+// fields are filtered.
+#[doc(hidden)]
+#[inline]
+pub fn __filtering() -> bool {
+    filter::filtering()
+}
+
+// Called by the derived code before a `tree` node, to restore its open state
+// after the node has been opened while filtering, so it isn't persisted.
+// `default_open` is the state of the node when it hasn't been toggled yet.
+#[doc(hidden)]
+pub fn __tree_state(label: &imgui::ImStr, default_open: bool) -> __TreeState {
+    if !filter::filtering() {
+        return __TreeState(None);
+    }
+    unsafe {
+        let storage = sys::igGetStateStorage();
+        let id = sys::igGetIDStr(label.as_ptr());
+        let open = sys::ImGuiStorage_GetInt(storage, id, default_open as _);
+        __TreeState(Some((storage, id, open)))
+    }
+}
+
+#[doc(hidden)]
+pub struct __TreeState(Option<(*mut sys::ImGuiStorage, sys::ImGuiID, i32)>);
+
+impl Drop for __TreeState {
+    fn drop(&mut self) {
+        if let Some((storage, id, open)) = self.0 {
+            unsafe { sys::ImGuiStorage_SetInt(storage, id, open) }
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct __Scope(());
+
+#[cfg(feature = "testing")]
+impl Drop for __Scope {
+    fn drop(&mut self) {
+        testing::end();
+    }
+}
+
+// Called by the derived code before the widgets of each field, with the
+// source of the annotation. The widgets are skipped when it returns `false`.
+#[doc(hidden)]
+#[inline]
+pub fn __begin_field(ui: &Ui, field: &'static str, _params: &'static str) -> bool {
+    highlight::begin(ui, field);
+    #[cfg(feature = "testing")]
+    testing::params(Some(_params));
+    let visible = filter::begin(field);
+    baseline::begin(ui, field, visible);
+    multi::begin(field, visible);
+    visible
+}
+
+// Called by the derived code after the widgets of each field.
+#[doc(hidden)]
+#[inline]
+pub fn __end_field(ui: &Ui) {
+    multi::end(ui);
+    baseline::end(ui);
+    filter::end();
+    highlight::end(ui);
+    #[cfg(feature = "testing")]
+    testing::params(None);
+}
+
+// Formats the values of the fields for the testing harness, using `Debug`
+// when the type implements it (autoref specialization).
+#[doc(hidden)]
+pub mod __value {
+    use std::fmt::Debug;
+
+    pub struct Value<'a, T>(pub &'a T);
+
+    pub trait DebugValue {
+        fn value(&self) -> Option<String>;
+    }
+
+    pub trait NoValue {
+        fn value(&self) -> Option<String>;
+    }
+
+    impl<T: Debug> DebugValue for &Value<'_, T> {
+        fn value(&self) -> Option<String> {
+            Some(format!("{:?}", self.0))
+        }
+    }
+
+    impl<T> NoValue for Value<'_, T> {
+        fn value(&self) -> Option<String> {
+            None
+        }
+    }
 }
 
 /// Extension trait for imgui's [`Ui`](https://docs.rs/imgui/*/imgui/struct.Ui.html).
@@ -490,6 +1057,42 @@ impl<T: Gui> Gui for Box<T> {
 /// ```
 pub trait UiExt {
     fn draw_gui<U: Gui>(&self, ext: &mut U) -> U::Events;
+
+    /// Renders all the widgets greyed out, without modifying `ext`.
+    fn draw_gui_readonly<U: Gui>(&self, ext: &U) -> U::Events;
+
+    /// Renders the buttons of an `#[imgui_ext::actions]` impl block.
+    fn draw_actions<A: Actions>(&self, ext: &mut A) -> A::Events;
+
+    /// Renders a search box followed by the widgets of the fields of `ext`
+    /// matching the text of the [filter](filter/index.html).
+    fn draw_gui_filtered<U: Gui + Visit>(
+        &self,
+        ext: &mut U,
+        filter: &mut filter::Filter,
+    ) -> U::Events;
+
+    /// Renders all the widgets, highlighting the fields that differ from
+    /// `baseline` (see the [baseline](baseline/index.html) module).
+    fn draw_gui_with_baseline<U>(
+        &self,
+        ext: &mut U,
+        baseline: &U,
+    ) -> baseline::BaselineEvents<U::Events>
+    where
+        U: Gui + baseline::Diff,
+        U::Events: baseline::DirtyEvents;
+
+    /// Renders the widgets of the first value of `ext`, writing the edited
+    /// fields into all of them (see the [multi](multi/index.html) module).
+    fn draw_gui_multi<U: Gui + baseline::Diff + Clone>(
+        &self,
+        ext: &mut [U],
+    ) -> Option<multi::MultiEvents<U::Events>>;
+
+    /// Renders the values of the [registry](registry/index.html) as a tree,
+    /// grouped by path. Returns the paths of the edited values.
+    fn draw_registry(&self) -> Vec<String>;
 }
 
 impl UiExt for Ui<'_> {
@@ -497,4 +1100,49 @@ impl UiExt for Ui<'_> {
     fn draw_gui<U: Gui>(&self, ext: &mut U) -> U::Events {
         U::draw_gui(self, ext)
     }
+
+    #[inline]
+    fn draw_gui_readonly<U: Gui>(&self, ext: &U) -> U::Events {
+        U::draw_gui_readonly(self, ext)
+    }
+
+    #[inline]
+    fn draw_actions<A: Actions>(&self, ext: &mut A) -> A::Events {
+        A::draw_actions(self, ext)
+    }
+
+    fn draw_gui_filtered<U: Gui + Visit>(
+        &self,
+        ext: &mut U,
+        filter: &mut filter::Filter,
+    ) -> U::Events {
+        filter.draw(self);
+        filter::with_filter::<U, _, _>(filter, || U::draw_gui(self, ext))
+    }
+
+    #[inline]
+    fn draw_gui_with_baseline<U>(
+        &self,
+        ext: &mut U,
+        baseline: &U,
+    ) -> baseline::BaselineEvents<U::Events>
+    where
+        U: Gui + baseline::Diff,
+        U::Events: baseline::DirtyEvents,
+    {
+        baseline::draw_gui(self, ext, baseline)
+    }
+
+    #[inline]
+    fn draw_gui_multi<U: Gui + baseline::Diff + Clone>(
+        &self,
+        ext: &mut [U],
+    ) -> Option<multi::MultiEvents<U::Events>> {
+        multi::draw_gui(self, ext)
+    }
+
+    #[inline]
+    fn draw_registry(&self) -> Vec<String> {
+        registry::draw(self)
+    }
 }
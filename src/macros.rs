@@ -6,7 +6,7 @@ macro_rules! imgui_drag_scalar {
 
                 let label = params.label.as_ptr();
                 let min = params.min.as_ref();
-                let max = params.min.as_ref();
+                let max = params.max.as_ref();
                 let format = ptr::null();
                 let speed = params.speed.unwrap_or(1.0);
                 let power = params.power.unwrap_or(1.0);
@@ -33,7 +33,7 @@ macro_rules! imgui_drag_scalar {
 
                 let label = params.label.as_ptr();
                 let min = params.min.as_ref();
-                let max = params.min.as_ref();
+                let max = params.max.as_ref();
                 let format = ptr::null();
                 let speed = params.speed.unwrap_or(1.0);
                 let power = params.power.unwrap_or(1.0);
@@ -282,7 +282,7 @@ macro_rules! imgui_drag_matrix {
 
                     let label = params.label.as_ptr();
                     let min = params.min.as_ref();
-                    let max = params.min.as_ref();
+                    let max = params.max.as_ref();
                     let format = std::ptr::null();
                     let speed = params.speed.unwrap_or(1.0);
                     let power = params.power.unwrap_or(1.0);
@@ -310,7 +310,7 @@ macro_rules! imgui_drag_matrix {
 
                         let label = params.label.as_ptr();
                         let min = params.min.as_ref();
-                        let max = params.min.as_ref();
+                        let max = params.max.as_ref();
                         let format = std::ptr::null();
                         let speed = params.speed.unwrap_or(1.0);
                         let power = params.power.unwrap_or(1.0);
@@ -409,3 +409,39 @@ macro_rules! imgui_slider_matrix {
         imgui_slider_matrix! { ($($tail),*), ($size-1), $size_2, $kind }
     }
 }
+
+macro_rules! imgui_clamp_scalar {
+    ( $( $scalar:ty ),* ) => {
+        $(
+            impl Clamp for $scalar {
+                type Elem = $scalar;
+                fn clamp(elem: &mut Self, min: $scalar, max: $scalar) {
+                    if *elem < min {
+                        *elem = min;
+                    } else if *elem > max {
+                        *elem = max;
+                    }
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! imgui_clamp_array {
+    ( $( $len:expr ),* ) => {
+        $(
+            // Clamps each of the elements.
+            impl<C: Clamp> Clamp for [C; $len]
+            where
+                C::Elem: Copy,
+            {
+                type Elem = C::Elem;
+                fn clamp(elem: &mut Self, min: C::Elem, max: C::Elem) {
+                    for elem in elem.iter_mut() {
+                        C::clamp(elem, min, max);
+                    }
+                }
+            }
+        )*
+    };
+}
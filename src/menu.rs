@@ -0,0 +1,146 @@
+//!
+//! Builds menus from structs of toggles and actions.
+//!
+//! * `menu(...)` renders a nested type (which must also
+//!   `#[derive(imgui_ext::Gui)]`) inside of a menu.
+//! * `menu_item(...)` renders a menu item. On a `bool` field, the item toggles
+//!   the value (and shows a check mark when `true`). On a `()` field, the item
+//!   is just an action.
+//!
+//! To place the menus in a menu bar, annotate the struct with:
+//!
+//! * `#[imgui(main_menu_bar)]` for a menu bar at the top of the screen.
+//! * `#[imgui(menu_bar)]` for the menu bar of the current window (which needs
+//!   the `MenuBar` window flag).
+//!
+//! ## `menu(...)` optional fields
+//!
+//! * `label` override menu label.
+//! * `catch`
+//! * `map` Applies a mapping function to `&mut Self`.
+//!
+//! ## `menu_item(...)` optional fields
+//!
+//! * `label` override item label.
+//! * `shortcut` shortcut text shown next to the label. It is only displayed,
+//!   the key presses must be handled by the application.
+//! * `catch`
+//! * `map` Applies a mapping function to `&mut Self`.
+//!
+//! ## Example
+//!
+//! ```
+//! use imgui_ext::UiExt;
+//!
+//! #[derive(imgui_ext::Gui)]
+//! #[imgui(main_menu_bar)]
+//! struct MainMenu {
+//!     #[imgui(menu(label = "File"))]
+//!     file: FileMenu,
+//!     #[imgui(menu(label = "View"))]
+//!     view: ViewMenu,
+//! }
+//!
+//! #[derive(imgui_ext::Gui)]
+//! struct FileMenu {
+//!     #[imgui(menu_item(label = "Open", shortcut = "Ctrl+O"))]
+//!     open: (),
+//!     #[imgui(menu_item(label = "Quit", shortcut = "Alt+F4"))]
+//!     quit: (),
+//! }
+//!
+//! #[derive(imgui_ext::Gui)]
+//! struct ViewMenu {
+//!     #[imgui(menu_item(label = "Wireframe"))]
+//!     wireframe: bool,
+//!     #[imgui(menu_item(label = "Show grid", shortcut = "G"))]
+//!     grid: bool,
+//! }
+//!
+//! # fn draw(ui: &imgui::Ui) {
+//! let mut menu = MainMenu {
+//!     file: FileMenu { open: (), quit: () },
+//!     view: ViewMenu { wireframe: false, grid: true },
+//! };
+//!
+//! if ui.draw_gui(&mut menu).file().quit() {
+//!     // exit the application...
+//! }
+//! # }
+//! ```
+use imgui::{ImStr, MenuItem as ImMenuItem, Ui};
+
+pub struct MenuItemParams<'a> {
+    pub label: &'a ImStr,
+    pub shortcut: Option<&'a ImStr>,
+    pub enabled: bool,
+}
+
+/// Trait for types that can be represented with a menu item.
+pub trait MenuItem {
+    fn build(ui: &Ui, elem: &mut Self, params: MenuItemParams) -> bool;
+
+    /// Renders a disabled menu item, without modifying the value.
+    fn build_readonly(ui: &Ui, elem: &Self, mut params: MenuItemParams)
+    where
+        Self: Clone,
+    {
+        params.enabled = false;
+        Self::build(ui, &mut elem.clone(), params);
+    }
+}
+
+impl MenuItem for bool {
+    fn build(ui: &Ui, elem: &mut Self, params: MenuItemParams) -> bool {
+        item(params).build_with_ref(ui, elem)
+    }
+}
+
+impl MenuItem for () {
+    fn build(ui: &Ui, _: &mut Self, params: MenuItemParams) -> bool {
+        item(params).build(ui)
+    }
+}
+
+impl<M: MenuItem> MenuItem for Option<M> {
+    fn build(ui: &Ui, elem: &mut Self, mut params: MenuItemParams) -> bool {
+        if let Some(ref mut elem) = elem {
+            M::build(ui, elem, params)
+        } else {
+            // keep the menu layout, but the item can't be clicked
+            params.enabled = false;
+            <() as MenuItem>::build(ui, &mut (), params)
+        }
+    }
+}
+
+impl<M: MenuItem> MenuItem for Box<M> {
+    #[inline]
+    fn build(ui: &Ui, elem: &mut Self, params: MenuItemParams) -> bool {
+        M::build(ui, elem, params)
+    }
+}
+
+// imgui menu item described by the params
+fn item(params: MenuItemParams) -> ImMenuItem {
+    let item = ImMenuItem::new(params.label).enabled(params.enabled);
+    match params.shortcut {
+        Some(shortcut) => item.shortcut(shortcut),
+        None => item,
+    }
+}
+
+/// Renders the contents of a menu, when it's open.
+pub fn menu<F: FnOnce()>(ui: &Ui, label: &ImStr, f: F) {
+    ui.menu(label, true, f);
+}
+
+/// Renders a menu bar at the top of the screen.
+pub fn main_menu_bar<F: FnOnce()>(ui: &Ui, f: F) {
+    ui.main_menu_bar(f);
+}
+
+/// Renders a menu bar at the top of the current window.
+pub fn menu_bar<F: FnOnce()>(ui: &Ui, f: F) {
+    ui.menu_bar(f);
+}
@@ -0,0 +1,166 @@
+//!
+//! Edits many values of the same type at once.
+//!
+//! [`UiExt::draw_gui_multi`] renders the UI of the first value. The fields
+//! whose value is not the same in all of them are marked as mixed (with
+//! [`MIXED`] next to their widgets), and the fields edited with the UI are
+//! written into every value.
+//!
+//! Fields are compared and copied with the [`Diff`] implementation of the
+//! type, so they need to implement `PartialEq` and `Clone` (the rest are never
+//! mixed, and only edited in the first value). Nested types are compared and
+//! copied field by field.
+//!
+//! # Example
+//!
+//! ```
+//! use imgui_ext::UiExt;
+//!
+//! #[derive(imgui_ext::Gui, Clone)]
+//! struct Entity {
+//!     #[imgui(drag)]
+//!     position: [f32; 3],
+//!     #[imgui(checkbox)]
+//!     visible: bool,
+//! }
+//!
+//! # fn frame(ui: &imgui::Ui, selection: &mut [Entity]) {
+//! if let Some(events) = ui.draw_gui_multi(selection) {
+//!     for field in events.edited() {
+//!         println!("{} edited in {} entities", field, selection.len());
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! [`UiExt::draw_gui_multi`]: ../trait.UiExt.html#tymethod.draw_gui_multi
+//! [`MIXED`]: ./constant.MIXED.html
+//! [`Diff`]: ../baseline/trait.Diff.html
+use imgui::Ui;
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use crate::baseline::{self, Diff};
+use crate::Gui;
+
+/// Text rendered next to the widgets of the mixed fields.
+pub const MIXED: &str = "--";
+
+/// Color of the `MIXED` text.
+pub const COLOR: [f32; 4] = [0.6, 0.6, 0.6, 1.0];
+
+/// Events returned by `UiExt::draw_gui_multi`.
+pub struct MultiEvents<E> {
+    /// Events of the first value.
+    pub events: E,
+    edited: Vec<String>,
+    mixed: Vec<String>,
+}
+
+impl<E> MultiEvents<E> {
+    /// Paths of the fields edited during this frame (and written into every
+    /// value).
+    pub fn edited(&self) -> &[String] {
+        &self.edited
+    }
+
+    /// Paths of the fields that are not the same in all the values (at the
+    /// end of the frame).
+    pub fn mixed(&self) -> &[String] {
+        &self.mixed
+    }
+
+    /// The field at `path` is not the same in all the values.
+    pub fn is_mixed(&self, path: &str) -> bool {
+        self.mixed.iter().any(|mixed| mixed == path)
+    }
+}
+
+/// Paths of the fields that are not the same in all the values.
+pub fn mixed<T: Diff>(values: &[T]) -> Vec<String> {
+    let mut mixed = Vec::new();
+    if let Some((first, rest)) = values.split_first() {
+        for value in rest {
+            for path in baseline::dirty(value, first) {
+                if !mixed.contains(&path) {
+                    mixed.push(path);
+                }
+            }
+        }
+    }
+    mixed
+}
+
+thread_local! {
+    // Mixed fields (if `draw_gui` is running).
+    static MULTI: RefCell<Option<Multi>> = RefCell::new(None);
+}
+
+struct Multi {
+    mixed: HashSet<String>,
+    // path of the field being drawn, and whether the field is visible
+    path: Vec<(&'static str, bool)>,
+}
+
+/// Renders the UI of the first value, and writes the edited fields into the
+/// rest. Returns `None` (and renders nothing) if `values` is empty.
+pub fn draw_gui<T: Gui + Diff + Clone>(
+    ui: &Ui,
+    values: &mut [T],
+) -> Option<MultiEvents<T::Events>> {
+    let (first, rest) = values.split_first_mut()?;
+
+    let multi = Multi {
+        mixed: mixed_with(first, rest),
+        path: Vec::new(),
+    };
+    let before = first.clone();
+    let prev = MULTI.with(|m| m.borrow_mut().replace(multi));
+    let events = T::draw_gui(ui, first);
+    MULTI.with(|m| *m.borrow_mut() = prev);
+
+    let edited = baseline::dirty(first, &before);
+    for value in rest.iter_mut() {
+        for path in edited.iter() {
+            T::revert(value, first, path);
+        }
+    }
+
+    Some(MultiEvents {
+        events,
+        edited,
+        mixed: mixed(values),
+    })
+}
+
+fn mixed_with<T: Diff>(first: &T, rest: &[T]) -> HashSet<String> {
+    rest.iter()
+        .flat_map(|value| baseline::dirty(value, first))
+        .collect()
+}
+
+/// Called before the widgets of a field.
+pub(crate) fn begin(field: &'static str, visible: bool) {
+    MULTI.with(|m| {
+        if let Some(m) = m.borrow_mut().as_mut() {
+            m.path.push((field, visible));
+        }
+    });
+}
+
+/// Called after the widgets of a field. Marks the mixed fields.
+pub(crate) fn end(ui: &Ui) {
+    MULTI.with(|m| {
+        if let Some(m) = m.borrow_mut().as_mut() {
+            let path: Vec<_> = m.path.iter().map(|(field, _)| *field).collect();
+            let mixed = m.mixed.contains(&path.join("."));
+            if let Some((_, true)) = m.path.pop() {
+                if mixed {
+                    ui.same_line(0.0);
+                    ui.text_colored(COLOR, MIXED);
+                }
+            }
+        }
+    });
+}
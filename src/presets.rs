@@ -0,0 +1,404 @@
+//!
+//! Named snapshots of a value, stored in a local directory (requires the
+//! `presets` feature).
+//!
+//! [`Presets`] renders a preset bar above the UI of the value:
+//!
+//! * A combo box to select one of the presets in the directory.
+//! * `Load` replaces the value with the selected preset.
+//! * `Delete` removes the selected preset from the directory.
+//! * `Revert` discards the edits made since the last load (or save).
+//! * A text input and a `Save` button to store the value with a new name.
+//!
+//! Presets are stored as JSON by default. RON and TOML are also supported,
+//! with the `ron` and `toml` features respectively.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use imgui_ext::presets::Presets;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(imgui_ext::Gui, Serialize, Deserialize)]
+//! struct Light {
+//!     #[imgui(slider(min = 0.0, max = 10.0))]
+//!     intensity: f32,
+//! }
+//!
+//! # fn frame(ui: &imgui::Ui, presets: &mut Presets, light: &mut Light) {
+//! let events = presets.draw_gui(ui, light);
+//!
+//! if let Some(name) = events.loaded() {
+//!     println!("Loaded preset {}", name);
+//! }
+//! if events.events.intensity() {
+//!     println!("Intensity changed");
+//! }
+//! # }
+//!
+//! let mut presets = Presets::new("presets/light");
+//! ```
+//!
+//! [`Presets`]: ./struct.Presets.html
+use imgui::{im_str, ComboBox, ImStr, ImString, Selectable, Ui};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::Gui;
+
+/// Error loading or storing a preset.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// The preset couldn't be serialized or deserialized.
+    Format(String),
+    /// Preset names can't be empty, nor contain path separators.
+    InvalidName(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "{}", err),
+            Error::Format(err) => write!(f, "{}", err),
+            Error::InvalidName(name) => write!(f, "Invalid preset name {:?}", name),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+/// Serialization format of the presets.
+pub trait Format {
+    /// Extension of the preset files (without the dot).
+    fn extension(&self) -> &str;
+    fn serialize<T: Serialize>(&self, value: &T) -> Result<String, Error>;
+    fn deserialize<T: DeserializeOwned>(&self, text: &str) -> Result<T, Error>;
+}
+
+/// JSON presets (`.json` files).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json;
+
+impl Format for Json {
+    fn extension(&self) -> &str {
+        "json"
+    }
+
+    fn serialize<T: Serialize>(&self, value: &T) -> Result<String, Error> {
+        serde_json::to_string_pretty(value).map_err(|err| Error::Format(err.to_string()))
+    }
+
+    fn deserialize<T: DeserializeOwned>(&self, text: &str) -> Result<T, Error> {
+        serde_json::from_str(text).map_err(|err| Error::Format(err.to_string()))
+    }
+}
+
+/// RON presets (`.ron` files).
+#[cfg(feature = "ron")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Ron;
+
+#[cfg(feature = "ron")]
+impl Format for Ron {
+    fn extension(&self) -> &str {
+        "ron"
+    }
+
+    fn serialize<T: Serialize>(&self, value: &T) -> Result<String, Error> {
+        ron::ser::to_string_pretty(value, Default::default())
+            .map_err(|err| Error::Format(err.to_string()))
+    }
+
+    fn deserialize<T: DeserializeOwned>(&self, text: &str) -> Result<T, Error> {
+        ron::de::from_str(text).map_err(|err| Error::Format(err.to_string()))
+    }
+}
+
+/// TOML presets (`.toml` files).
+#[cfg(feature = "toml")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Toml;
+
+#[cfg(feature = "toml")]
+impl Format for Toml {
+    fn extension(&self) -> &str {
+        "toml"
+    }
+
+    fn serialize<T: Serialize>(&self, value: &T) -> Result<String, Error> {
+        toml::to_string_pretty(value).map_err(|err| Error::Format(err.to_string()))
+    }
+
+    fn deserialize<T: DeserializeOwned>(&self, text: &str) -> Result<T, Error> {
+        toml::from_str(text).map_err(|err| Error::Format(err.to_string()))
+    }
+}
+
+/// Events returned by `Presets::draw_gui`.
+pub struct PresetEvents<E> {
+    /// Events of the value.
+    pub events: E,
+    loaded: Option<String>,
+    saved: Option<String>,
+    deleted: Option<String>,
+    reverted: bool,
+}
+
+impl<E> PresetEvents<E> {
+    /// Name of the preset loaded into the value (if any).
+    pub fn loaded(&self) -> Option<&str> {
+        self.loaded.as_ref().map(String::as_str)
+    }
+
+    /// Name of the preset the value was saved as (if any).
+    pub fn saved(&self) -> Option<&str> {
+        self.saved.as_ref().map(String::as_str)
+    }
+
+    /// Name of the deleted preset (if any).
+    pub fn deleted(&self) -> Option<&str> {
+        self.deleted.as_ref().map(String::as_str)
+    }
+
+    /// The edits were discarded with the `Revert` button.
+    pub fn reverted(&self) -> bool {
+        self.reverted
+    }
+}
+
+/// Preset bar, storing the presets in a directory.
+pub struct Presets<F = Json> {
+    dir: PathBuf,
+    format: F,
+    names: Vec<String>,
+    selected: Option<usize>,
+    name: ImString,
+    // serialized value of the last load or save, restored by `Revert`
+    revert: Option<String>,
+    error: Option<String>,
+}
+
+impl Presets<Json> {
+    /// Creates a preset bar storing JSON files in `dir`. The directory is
+    /// created when the first preset is saved.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self::with_format(dir, Json)
+    }
+}
+
+impl<F: Format> Presets<F> {
+    pub fn with_format<P: Into<PathBuf>>(dir: P, format: F) -> Self {
+        let mut presets = Self {
+            dir: dir.into(),
+            format,
+            names: Vec::new(),
+            selected: None,
+            name: ImString::with_capacity(64),
+            revert: None,
+            error: None,
+        };
+        // a missing directory has no presets
+        let _ = presets.refresh();
+        presets
+    }
+
+    /// Directory of the presets.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Names of the presets (without the extension), sorted.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Name of the selected preset.
+    pub fn selected(&self) -> Option<&str> {
+        self.selected.map(|i| self.names[i].as_str())
+    }
+
+    /// Reads the names of the presets from the directory.
+    pub fn refresh(&mut self) -> Result<(), Error> {
+        let selected = self.selected().map(String::from);
+        self.names.clear();
+        self.selected = None;
+
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            let ext = path.extension().and_then(|ext| ext.to_str());
+            let stem = path.file_stem().and_then(|stem| stem.to_str());
+            if let (Some(ext), Some(stem)) = (ext, stem) {
+                if ext == self.format.extension() {
+                    self.names.push(stem.to_string());
+                }
+            }
+        }
+        self.names.sort();
+        self.selected = selected.and_then(|s| self.names.iter().position(|n| *n == s));
+        Ok(())
+    }
+
+    /// Stores `value` as the preset `name`, replacing it if it exists.
+    pub fn save<T: Serialize>(&mut self, name: &str, value: &T) -> Result<(), Error> {
+        let text = self.format.serialize(value)?;
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.path(name)?, &text)?;
+        self.revert = Some(text);
+        self.refresh()?;
+        self.selected = self.names.iter().position(|n| n == name);
+        Ok(())
+    }
+
+    /// Reads the preset `name`.
+    pub fn load<T: DeserializeOwned>(&mut self, name: &str) -> Result<T, Error> {
+        let text = fs::read_to_string(self.path(name)?)?;
+        let value = self.format.deserialize(&text)?;
+        self.revert = Some(text);
+        self.selected = self.names.iter().position(|n| n == name);
+        Ok(value)
+    }
+
+    /// Removes the preset `name` from the directory.
+    pub fn delete(&mut self, name: &str) -> Result<(), Error> {
+        fs::remove_file(self.path(name)?)?;
+        self.refresh()
+    }
+
+    /// Value of the last load or save.
+    pub fn revert<T: DeserializeOwned>(&self) -> Option<Result<T, Error>> {
+        self.revert
+            .as_ref()
+            .map(|text| self.format.deserialize(text))
+    }
+
+    fn path(&self, name: &str) -> Result<PathBuf, Error> {
+        if name.is_empty()
+            || name.contains(|c: char| c == '/' || c == '\\')
+            || name.starts_with('.')
+        {
+            return Err(Error::InvalidName(name.to_string()));
+        }
+        Ok(self
+            .dir
+            .join(format!("{}.{}", name, self.format.extension())))
+    }
+
+    /// Renders the preset bar followed by the UI of `value`.
+    pub fn draw_gui<T>(&mut self, ui: &Ui, value: &mut T) -> PresetEvents<T::Events>
+    where
+        T: Gui + Serialize + DeserializeOwned,
+    {
+        let mut loaded = None;
+        let mut saved = None;
+        let mut deleted = None;
+        let mut reverted = false;
+
+        let id = ui.push_id("imgui_ext_presets");
+
+        let preview = self.selected().map(String::from).unwrap_or_default();
+        if let Some(i) = combo(ui, im_str!("Preset"), &preview, &self.names) {
+            self.selected = Some(i);
+        }
+
+        let selected = self.selected().map(String::from);
+        ui.same_line(0.0);
+        if ui.button(im_str!("Load"), [0.0, 0.0]) {
+            if let Some(name) = selected.as_ref() {
+                match self.load(name) {
+                    Ok(v) => {
+                        *value = v;
+                        loaded = selected.clone();
+                        self.error = None;
+                    }
+                    Err(err) => self.error = Some(err.to_string()),
+                }
+            }
+        }
+        ui.same_line(0.0);
+        if ui.button(im_str!("Delete"), [0.0, 0.0]) {
+            if let Some(name) = selected.as_ref() {
+                match self.delete(name) {
+                    Ok(()) => {
+                        deleted = selected.clone();
+                        self.error = None;
+                    }
+                    Err(err) => self.error = Some(err.to_string()),
+                }
+            }
+        }
+        ui.same_line(0.0);
+        if ui.button(im_str!("Revert"), [0.0, 0.0]) {
+            match self.revert() {
+                Some(Ok(v)) => {
+                    *value = v;
+                    reverted = true;
+                    self.error = None;
+                }
+                Some(Err(err)) => self.error = Some(err.to_string()),
+                None => {}
+            }
+        }
+
+        ui.input_text(im_str!("Name"), &mut self.name).build();
+        ui.same_line(0.0);
+        if ui.button(im_str!("Save"), [0.0, 0.0]) {
+            let name = self.name.to_str().trim().to_string();
+            match self.save(&name, value) {
+                Ok(()) => {
+                    saved = Some(name);
+                    self.name.clear();
+                    self.error = None;
+                }
+                Err(err) => self.error = Some(err.to_string()),
+            }
+        }
+
+        if let Some(error) = self.error.as_ref() {
+            ui.text_colored([1.0, 0.0, 0.0, 1.0], error);
+        }
+
+        id.pop(ui);
+        ui.separator();
+
+        PresetEvents {
+            events: T::draw_gui(ui, value),
+            loaded,
+            saved,
+            deleted,
+            reverted,
+        }
+    }
+}
+
+// Combo box listing the names. Returns the index of the clicked name.
+fn combo(ui: &Ui, label: &ImStr, preview: &str, names: &[String]) -> Option<usize> {
+    let preview = label_text(preview);
+    let mut clicked = None;
+    ComboBox::new(label).preview_value(&preview).build(ui, || {
+        for (i, name) in names.iter().enumerate() {
+            let id = ui.push_id(i as i32);
+            if Selectable::new(&label_text(name)).build(ui) {
+                clicked = Some(i);
+            }
+            id.pop(ui);
+        }
+    });
+    clicked
+}
+
+// Label of a name. Labels end at the first NUL, so they are escaped.
+fn label_text(name: &str) -> ImString {
+    ImString::new(name.replace('\0', "\\0"))
+}
@@ -33,6 +33,11 @@ pub struct ProgressParams<'a> {
 
 pub trait Progress {
     fn build(ui: &Ui, elem: &Self, params: ProgressParams);
+
+    /// Renders the progress bar greyed out.
+    fn build_readonly(ui: &Ui, elem: &Self, params: ProgressParams) {
+        crate::readonly(ui, || Self::build(ui, elem, params));
+    }
 }
 
 impl Progress for f32 {
@@ -0,0 +1,343 @@
+//!
+//! Registry of tweakable values, rendered without a struct to hold them.
+//!
+//! Values are registered with a path, such as `"render/shadows/bias"`, and a
+//! [`Tweak`] describing their widget (the equivalent of an annotation). They
+//! are rendered by [`UiExt::draw_registry`] as a tree, grouped by path, using
+//! the same widget traits as the derive macro.
+//!
+//! The registry is thread local: values must be registered in the thread
+//! that renders the UI.
+//!
+//! # Values owned by the registry
+//!
+//! [`tweak`] (or the `tweak!` macro) registers a value the first time it is
+//! called for a path, and returns a copy of the current value on every call.
+//! It can be used in place of a constant:
+//!
+//! ```
+//! use imgui_ext::tweak;
+//!
+//! fn shadow_bias() -> f32 {
+//!     tweak!("render/shadows/bias", 0.005, drag(0.0001))
+//! }
+//!
+//! fn draw_shadows() -> bool {
+//!     tweak!("render/shadows/enabled", true, checkbox())
+//! }
+//! ```
+//!
+//! # Shared values
+//!
+//! Values owned elsewhere are registered with [`register`], either as a
+//! `&'static Mutex<T>`, an `Arc<Mutex<T>>` or an `Rc<RefCell<T>>`:
+//!
+//! ```
+//! use imgui_ext::registry::{self, Tweak};
+//! use std::sync::{Arc, Mutex};
+//!
+//! let exposure = Arc::new(Mutex::new(1.0_f32));
+//! registry::register("render/exposure", exposure.clone(), Tweak::slider(0.0, 4.0));
+//! ```
+//!
+//! ```
+//! use imgui_ext::UiExt;
+//!
+//! # fn frame(ui: &imgui::Ui) {
+//! for path in ui.draw_registry() {
+//!     println!("{} changed", path);
+//! }
+//! # }
+//! ```
+//!
+//! [`Tweak`]: ./struct.Tweak.html
+//! [`tweak`]: ./fn.tweak.html
+//! [`register`]: ./fn.register.html
+//! [`UiExt::draw_registry`]: ../trait.UiExt.html#tymethod.draw_registry
+use imgui::{ImStr, ImString, TreeNode, Ui};
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+use crate::checkbox::{Checkbox, CheckboxParams};
+use crate::color::{ColorEdit, ColorEditParams};
+use crate::drag::{Drag, DragParams};
+use crate::input::{Input, InputParams};
+use crate::slider::{Slider, SliderParams};
+
+/// Widget of a registered value (the equivalent of an annotation).
+pub struct Tweak<T> {
+    build: Box<dyn Fn(&Ui, &ImStr, &mut T) -> bool>,
+}
+
+impl<T: 'static> Tweak<T> {
+    /// Builds the widget with a custom function. Returns `true` when the
+    /// value is edited.
+    pub fn new<F>(build: F) -> Self
+    where
+        F: Fn(&Ui, &ImStr, &mut T) -> bool + 'static,
+    {
+        Self {
+            build: Box::new(build),
+        }
+    }
+
+    /// `slider(min = .., max = ..)`
+    pub fn slider<P: Copy + 'static>(min: P, max: P) -> Self
+    where
+        T: Slider<P>,
+    {
+        Self::new(move |ui, label, value| {
+            let params = SliderParams {
+                min,
+                max,
+                label,
+                format: None,
+                power: None,
+            };
+            Slider::build(ui, value, params)
+        })
+    }
+
+    /// `drag(speed = ..)`
+    pub fn drag<P: 'static>(speed: f32) -> Self
+    where
+        T: Drag<P>,
+    {
+        Self::new(move |ui, label, value| {
+            let params = DragParams {
+                label,
+                format: None,
+                min: None,
+                max: None,
+                speed: Some(speed),
+                power: None,
+            };
+            Drag::build(ui, value, params)
+        })
+    }
+
+    /// `input`
+    pub fn input<P: 'static>() -> Self
+    where
+        T: Input<P>,
+    {
+        Self::new(|ui, label, value| {
+            let params = InputParams {
+                label,
+                step: None,
+                step_fast: None,
+                flags: None,
+                size: None,
+            };
+            Input::build(ui, value, params)
+        })
+    }
+
+    /// `checkbox`
+    pub fn checkbox() -> Self
+    where
+        T: Checkbox,
+    {
+        Self::new(|ui, label, value| Checkbox::build(ui, value, CheckboxParams { label }))
+    }
+
+    /// `color(edit)`
+    pub fn color() -> Self
+    where
+        for<'a> &'a mut T: ColorEdit,
+    {
+        Self::new(|ui, label, value| {
+            let params = ColorEditParams {
+                label,
+                flags: None,
+                preview: None,
+                format: None,
+                input_mode: None,
+                display_mode: None,
+            };
+            ColorEdit::build(ui, value, params)
+        })
+    }
+}
+
+/// Storage of a registered value.
+pub trait Source<T>: 'static {
+    /// Runs `f` with the value. Returns `None` if the value couldn't be
+    /// borrowed (or the lock is poisoned).
+    fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R>;
+}
+
+impl<T: 'static> Source<T> for &'static Mutex<T> {
+    fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        self.lock().ok().map(|mut value| f(&mut value))
+    }
+}
+
+impl<T: 'static> Source<T> for Arc<Mutex<T>> {
+    fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        self.lock().ok().map(|mut value| f(&mut value))
+    }
+}
+
+impl<T: 'static> Source<T> for Rc<RefCell<T>> {
+    fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        self.try_borrow_mut().ok().map(|mut value| f(&mut value))
+    }
+}
+
+// Type erased registered value.
+trait Entry {
+    fn draw(&self, ui: &Ui, label: &ImStr) -> bool;
+    fn source(&self) -> &dyn Any;
+}
+
+struct Item<T, S> {
+    source: S,
+    tweak: Tweak<T>,
+}
+
+impl<T: 'static, S: Source<T>> Entry for Item<T, S> {
+    fn draw(&self, ui: &Ui, label: &ImStr) -> bool {
+        let tweak = &self.tweak;
+        self.source
+            .with(|value| (tweak.build)(ui, label, value))
+            .unwrap_or(false)
+    }
+
+    fn source(&self) -> &dyn Any {
+        &self.source
+    }
+}
+
+thread_local! {
+    // Registered values, sorted by path.
+    static REGISTRY: RefCell<BTreeMap<String, Rc<dyn Entry>>> = RefCell::new(BTreeMap::new());
+}
+
+/// Registers a value, replacing the value registered with the same path.
+///
+/// The segments of the path are separated by `/`. The last one is the label
+/// of the widget.
+pub fn register<T, S>(path: &str, source: S, tweak: Tweak<T>)
+where
+    T: 'static,
+    S: Source<T>,
+{
+    let item = Item { source, tweak };
+    REGISTRY.with(|r| r.borrow_mut().insert(path.to_string(), Rc::new(item)));
+}
+
+/// Removes a value from the registry.
+pub fn unregister(path: &str) {
+    REGISTRY.with(|r| r.borrow_mut().remove(path));
+}
+
+/// Paths of the registered values, sorted.
+pub fn paths() -> Vec<String> {
+    REGISTRY.with(|r| r.borrow().keys().cloned().collect())
+}
+
+/// Returns the value registered with `path`, registering `init` (with the
+/// widget returned by `widget`) the first time.
+///
+/// # Panics
+///
+/// If the value registered with `path` is not a `Rc<RefCell<T>>` (it was
+/// registered with a different type).
+pub fn tweak<T, F>(path: &str, init: T, widget: F) -> T
+where
+    T: Clone + 'static,
+    F: FnOnce() -> Tweak<T>,
+{
+    let value = REGISTRY.with(|r| {
+        r.borrow().get(path).map(
+            |entry| match entry.source().downcast_ref::<Rc<RefCell<T>>>() {
+                Some(value) => value.clone(),
+                None => panic!("Tweak {:?} was registered with a different type", path),
+            },
+        )
+    });
+    match value {
+        Some(value) => value.borrow().clone(),
+        None => {
+            register(path, Rc::new(RefCell::new(init.clone())), widget());
+            init
+        }
+    }
+}
+
+/// Shorthand for [`registry::tweak`](./registry/fn.tweak.html), with the
+/// widget written like an annotation (`slider(0.0, 1.0)`, `checkbox()`, ...)
+///
+/// ```
+/// let bias = imgui_ext::tweak!("render/shadows/bias", 0.005_f32, drag(0.0001));
+/// ```
+#[macro_export]
+macro_rules! tweak {
+    ($path:expr, $init:expr, $kind:ident ( $($arg:expr),* )) => {
+        $crate::registry::tweak($path, $init, || $crate::registry::Tweak::$kind($($arg),*))
+    };
+}
+
+// Renders the registered values. Returns the paths of the edited values.
+//
+// The values are drawn from a snapshot of the registry, so they can be
+// registered and unregistered while drawing (by a `tweak!` in an `on_change`
+// callback, for example).
+pub(crate) fn draw(ui: &Ui) -> Vec<String> {
+    let mut edited = Vec::new();
+    let registry: Vec<(String, Rc<dyn Entry>)> = REGISTRY.with(|r| {
+        r.borrow()
+            .iter()
+            .map(|(path, entry)| (path.clone(), entry.clone()))
+            .collect()
+    });
+    let entries: Vec<(Vec<_>, _, _)> = registry
+        .iter()
+        .map(|(path, entry)| (path.split('/').collect(), path.as_str(), entry.as_ref()))
+        .collect();
+    draw_group(ui, &entries, 0, &mut edited);
+    edited
+}
+
+// Renders the entries sharing the first `depth` segments of their paths.
+// Entries are sorted, so each group is a contiguous range.
+fn draw_group(
+    ui: &Ui,
+    entries: &[(Vec<&str>, &str, &dyn Entry)],
+    depth: usize,
+    edited: &mut Vec<String>,
+) {
+    let mut i = 0;
+    while i < entries.len() {
+        let (segments, path, entry) = &entries[i];
+        if segments.len() == depth + 1 {
+            // leaves and groups with the same label (`a/b` and `a/b/c`) get
+            // different ids
+            let label = ImString::new(segments[depth]);
+            let id = ui.push_id("leaf");
+            if entry.draw(ui, &label) {
+                edited.push(path.to_string());
+            }
+            id.pop(ui);
+            i += 1;
+        } else {
+            let group = segments[depth];
+            let len = entries[i..]
+                .iter()
+                .take_while(|(s, _, _)| s.len() > depth + 1 && s[depth] == group)
+                .count();
+            let label = ImString::new(group);
+            let id = ui.push_id("group");
+            TreeNode::new(ui, &label).build(|| {
+                draw_group(ui, &entries[i..i + len], depth + 1, edited);
+            });
+            id.pop(ui);
+            i += len;
+        }
+    }
+}
@@ -0,0 +1,514 @@
+//!
+//! Inspection of values over a local socket (requires the `remote` feature).
+//!
+//! A [`Server`] holds a registry of named values and serves them to other
+//! processes over TCP (or a Unix socket), so they can be inspected and
+//! tweaked when there is no window to draw the UI into.
+//!
+//! # Protocol
+//!
+//! Each request is a JSON object in a single line. Each response is also a
+//! single line, either `{"ok": ...}` or `{"error": "..."}`.
+//!
+//! * `{"cmd": "list"}` returns the [`Schema`] of every value, by name.
+//! * `{"cmd": "get", "name": "...", "path": "..."}` returns the value (or one
+//!   of its fields). `path` is optional, with nested fields separated by dots
+//!   (`"light.color"`).
+//! * `{"cmd": "set", "name": "...", "path": "...", "value": ...}` replaces the
+//!   value (or one of its annotated fields).
+//! * `{"cmd": "press", "name": "...", "button": "..."}` presses one of the
+//!   buttons of the value (by label). Presses are queued until the
+//!   application reads them with `Server::pressed`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use imgui_ext::remote::{Client, Server};
+//! use serde::{Deserialize, Serialize};
+//! use std::sync::{Arc, Mutex};
+//!
+//! #[derive(imgui_ext::Gui, Serialize, Deserialize)]
+//! struct Light {
+//!     #[imgui(slider(min = 0.0, max = 10.0), button(label = "Reset"))]
+//!     intensity: f32,
+//! }
+//!
+//! let light = Arc::new(Mutex::new(Light { intensity: 1.0 }));
+//!
+//! let server = Server::bind("127.0.0.1:0").unwrap();
+//! server.register("light", light.clone());
+//!
+//! // in another process...
+//! let mut client = Client::connect(server.local_addr()).unwrap();
+//! client.set("light", "intensity", 4.0.into()).unwrap();
+//! client.press("light", "Reset").unwrap();
+//!
+//! // back in the application
+//! for button in server.pressed("light") {
+//!     if button == "Reset" {
+//!         light.lock().unwrap().intensity = 1.0;
+//!     }
+//! }
+//! ```
+//!
+//! [`Server`]: ./struct.Server.html
+//! [`Schema`]: ../schema/struct.Schema.html
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::schema::{Node, Schema};
+use crate::Visit;
+
+// Type erased value of the registry.
+trait Entry: Send + Sync {
+    fn get(&self) -> Result<Value, String>;
+    fn set(&self, value: Value) -> Result<(), String>;
+    /// Replaces the field at `path` (while the value is locked).
+    fn set_field(&self, path: &str, value: Value) -> Result<(), String>;
+}
+
+impl<T> Entry for Arc<Mutex<T>>
+where
+    T: Serialize + DeserializeOwned + Send,
+{
+    fn get(&self) -> Result<Value, String> {
+        let value = self.lock().map_err(|err| err.to_string())?;
+        serde_json::to_value(&*value).map_err(|err| err.to_string())
+    }
+
+    fn set(&self, value: Value) -> Result<(), String> {
+        let value = serde_json::from_value(value).map_err(|err| err.to_string())?;
+        *self.lock().map_err(|err| err.to_string())? = value;
+        Ok(())
+    }
+
+    fn set_field(&self, path: &str, value: Value) -> Result<(), String> {
+        let mut lock = self.lock().map_err(|err| err.to_string())?;
+        let mut current = serde_json::to_value(&*lock).map_err(|err| err.to_string())?;
+        match current.pointer_mut(&pointer(path)) {
+            Some(current) => *current = value,
+            None => return Err(format!("Field {:?} not found", path)),
+        }
+        *lock = serde_json::from_value(current).map_err(|err| err.to_string())?;
+        Ok(())
+    }
+}
+
+// Value of the registry. The connections clone it out of the registry, so the
+// registry is not locked while the value is.
+type Shared = Arc<(Schema, Box<dyn Entry>)>;
+
+#[derive(Default)]
+struct Registry {
+    entries: BTreeMap<String, Shared>,
+    pressed: HashMap<String, Vec<String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+enum Request {
+    List,
+    Get {
+        name: String,
+        #[serde(default)]
+        path: Option<String>,
+    },
+    Set {
+        name: String,
+        #[serde(default)]
+        path: Option<String>,
+        value: Value,
+    },
+    Press {
+        name: String,
+        button: String,
+    },
+}
+
+impl Registry {
+    // The registry is only locked to look up the values, so a value locked by
+    // the application doesn't block `register` and `unregister`.
+    fn handle(registry: &Mutex<Self>, request: Request) -> Result<Value, String> {
+        let entry = |name: &str| registry.lock().unwrap().entry(name);
+        match request {
+            Request::List => {
+                let registry = registry.lock().unwrap();
+                let schemas: BTreeMap<_, _> = registry
+                    .entries
+                    .iter()
+                    .map(|(name, entry)| (name, &entry.0))
+                    .collect();
+                serde_json::to_value(schemas).map_err(|err| err.to_string())
+            }
+            Request::Get { name, path } => {
+                let value = entry(&name)?.1.get()?;
+                match path {
+                    None => Ok(value),
+                    Some(path) => value
+                        .pointer(&pointer(&path))
+                        .cloned()
+                        .ok_or_else(|| format!("Field {:?} not found", path)),
+                }
+            }
+            Request::Set { name, path, value } => {
+                let entry = entry(&name)?;
+                let (schema, entry) = &*entry;
+                match path {
+                    None => entry.set(value),
+                    Some(path) => {
+                        // only annotated fields can be set
+                        let fields: Vec<_> = path.split('.').collect();
+                        if !annotated(&schema.widgets, &fields) {
+                            return Err(format!("Field {:?} is not annotated", path));
+                        }
+                        entry.set_field(&path, value)
+                    }
+                }
+                .map(|_| Value::Null)
+            }
+            Request::Press { name, button } => {
+                let mut registry = registry.lock().unwrap();
+                let entry = registry.entry(&name)?;
+                if !has_button(&entry.0.widgets, &button) {
+                    return Err(format!("Button {:?} not found", button));
+                }
+                registry.pressed.entry(name).or_default().push(button);
+                Ok(Value::Null)
+            }
+        }
+    }
+
+    fn entry(&self, name: &str) -> Result<Shared, String> {
+        self.entries
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("Value {:?} not found", name))
+    }
+}
+
+// "light.color" -> "/light/color"
+fn pointer(path: &str) -> String {
+    path.split('.').map(|field| format!("/{}", field)).collect()
+}
+
+// Whether every field of the path is annotated. The widgets of `nested` and
+// `menu` (without a `map`) are the fields of the type of the field, the ones
+// of other containers belong to the same field.
+fn annotated(nodes: &[Node], path: &[&str]) -> bool {
+    let (field, rest) = match path.split_first() {
+        Some(split) => split,
+        None => return false,
+    };
+    nodes
+        .iter()
+        .filter(|node| node.field == *field)
+        .any(|node| match node.kind.as_str() {
+            "nested" | "menu" if !node.params.contains_key("map") => {
+                rest.is_empty() || annotated(&node.children, rest)
+            }
+            "button" => false,
+            _ if rest.is_empty() => true,
+            _ => annotated(&node.children, path),
+        })
+}
+
+fn has_button(nodes: &[Node], label: &str) -> bool {
+    nodes.iter().any(|node| {
+        (node.kind == "button" && node.label == label) || has_button(&node.children, label)
+    })
+}
+
+// Maximum length of a request (in bytes, including the line break).
+const MAX_REQUEST: u64 = 1 << 20;
+
+// Handles the requests of a connection until it is closed.
+fn serve<S: io::Read + Write>(stream: S, registry: Arc<Mutex<Registry>>) -> io::Result<()> {
+    let mut stream = BufReader::new(stream);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let len = stream.by_ref().take(MAX_REQUEST).read_line(&mut line)?;
+        if len == 0 {
+            return Ok(());
+        }
+        if len as u64 == MAX_REQUEST && !line.ends_with('\n') {
+            // the rest of the line can't be told apart from the next request,
+            // so the connection is closed
+            let stream = stream.get_mut();
+            writeln!(stream, "{}", json!({ "error": "Request too long" }))?;
+            stream.flush()?;
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Request too long",
+            ));
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str(&line) {
+            Ok(request) => match Registry::handle(&registry, request) {
+                Ok(value) => json!({ "ok": value }),
+                Err(error) => json!({ "error": error }),
+            },
+            Err(error) => json!({ "error": error.to_string() }),
+        };
+        let stream = stream.get_mut();
+        writeln!(stream, "{}", response)?;
+        stream.flush()?;
+    }
+}
+
+// Open connections, shut down when the server is dropped.
+#[derive(Default)]
+struct Connections {
+    closed: bool,
+    next: usize,
+    shutdown: HashMap<usize, Box<dyn FnOnce() + Send>>,
+}
+
+// Serves a connection in a background thread, until it is closed (by the
+// client, or by `shutdown` when the server is dropped).
+fn spawn<S, F>(
+    stream: S,
+    shutdown: F,
+    registry: Arc<Mutex<Registry>>,
+    connections: Arc<Mutex<Connections>>,
+) where
+    S: io::Read + Write + Send + 'static,
+    F: FnOnce() + Send + 'static,
+{
+    let id = {
+        let mut connections = connections.lock().unwrap();
+        if connections.closed {
+            return;
+        }
+        let id = connections.next;
+        connections.next += 1;
+        connections.shutdown.insert(id, Box::new(shutdown));
+        id
+    };
+    thread::spawn(move || {
+        let _ = serve(stream, registry);
+        connections.lock().unwrap().shutdown.remove(&id);
+    });
+}
+
+/// Serves a registry of values. The connections are handled in background
+/// threads. Dropping the server stops listening and shuts down the open
+/// connections.
+pub struct Server {
+    addr: SocketAddr,
+    registry: Arc<Mutex<Registry>>,
+    closed: Arc<AtomicBool>,
+    connections: Arc<Mutex<Connections>>,
+}
+
+impl Server {
+    /// Listens for TCP connections. Use port 0 to pick a free port.
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let registry = Arc::new(Mutex::new(Registry::default()));
+        let closed = Arc::new(AtomicBool::new(false));
+        let connections = Arc::new(Mutex::new(Connections::default()));
+
+        let (reg, cl, conns) = (registry.clone(), closed.clone(), connections.clone());
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if cl.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Ok((stream, clone)) = stream.and_then(|s| Ok((s.try_clone()?, s))) {
+                    let shutdown = move || {
+                        let _ = clone.shutdown(Shutdown::Both);
+                    };
+                    spawn(stream, shutdown, reg.clone(), conns.clone());
+                }
+            }
+        });
+
+        Ok(Self {
+            addr,
+            registry,
+            closed,
+            connections,
+        })
+    }
+
+    /// Address the server is listening on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Adds a value to the registry, replacing the value with the same name.
+    pub fn register<T>(&self, name: &str, value: Arc<Mutex<T>>)
+    where
+        T: Visit + Serialize + DeserializeOwned + Send + 'static,
+    {
+        let entry = Arc::new((Schema::of::<T>(), Box::new(value) as Box<dyn Entry>));
+        let mut registry = self.registry.lock().unwrap();
+        registry.entries.insert(name.to_string(), entry);
+    }
+
+    /// Removes a value from the registry.
+    pub fn unregister(&self, name: &str) {
+        let mut registry = self.registry.lock().unwrap();
+        registry.entries.remove(name);
+        registry.pressed.remove(name);
+    }
+
+    /// Labels of the buttons of the value pressed by the clients since the
+    /// last call, in order.
+    pub fn pressed(&self, name: &str) -> Vec<String> {
+        let mut registry = self.registry.lock().unwrap();
+        registry.pressed.remove(name).unwrap_or_default()
+    }
+}
+
+impl Server {
+    // Shuts down the open connections (and the ones being accepted).
+    fn shutdown(&self) {
+        let mut connections = self.connections.lock().unwrap();
+        connections.closed = true;
+        for (_, shutdown) in connections.shutdown.drain() {
+            shutdown();
+        }
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.closed.store(true, Ordering::SeqCst);
+        // wake up the listener thread
+        let _ = TcpStream::connect(self.addr);
+        self.shutdown();
+    }
+}
+
+/// Unix socket version of [`Server`](./struct.Server.html).
+#[cfg(unix)]
+pub struct UnixServer {
+    path: std::path::PathBuf,
+    server: Server,
+}
+
+#[cfg(unix)]
+impl UnixServer {
+    /// Listens for connections on the socket at `path`.
+    pub fn bind<P: AsRef<std::path::Path>>(path: P) -> io::Result<Self> {
+        use std::os::unix::net::UnixListener;
+
+        let path = path.as_ref().to_path_buf();
+        let listener = UnixListener::bind(&path)?;
+        let registry = Arc::new(Mutex::new(Registry::default()));
+        let closed = Arc::new(AtomicBool::new(false));
+        let connections = Arc::new(Mutex::new(Connections::default()));
+
+        let (reg, cl, conns) = (registry.clone(), closed.clone(), connections.clone());
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if cl.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Ok((stream, clone)) = stream.and_then(|s| Ok((s.try_clone()?, s))) {
+                    let shutdown = move || {
+                        let _ = clone.shutdown(Shutdown::Both);
+                    };
+                    spawn(stream, shutdown, reg.clone(), conns.clone());
+                }
+            }
+        });
+
+        // the TCP address is never used
+        let addr = ([0, 0, 0, 0], 0).into();
+        let server = Server {
+            addr,
+            registry,
+            closed,
+            connections,
+        };
+        Ok(Self { path, server })
+    }
+}
+
+#[cfg(unix)]
+impl std::ops::Deref for UnixServer {
+    type Target = Server;
+
+    fn deref(&self) -> &Server {
+        &self.server
+    }
+}
+
+#[cfg(unix)]
+impl Drop for UnixServer {
+    fn drop(&mut self) {
+        self.server.closed.store(true, Ordering::SeqCst);
+        let _ = std::os::unix::net::UnixStream::connect(&self.path);
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Client of a [`Server`](./struct.Server.html).
+pub struct Client {
+    stream: BufReader<TcpStream>,
+}
+
+impl Client {
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        Ok(Self {
+            stream: BufReader::new(TcpStream::connect(addr)?),
+        })
+    }
+
+    /// Sends a request and waits for the response. Returns the contents of
+    /// `ok`, or the contents of `error` as an error.
+    pub fn request(&mut self, request: &Value) -> io::Result<Result<Value, String>> {
+        let stream = self.stream.get_mut();
+        writeln!(stream, "{}", request)?;
+        stream.flush()?;
+
+        let mut line = String::new();
+        self.stream.read_line(&mut line)?;
+        let mut response: Value = serde_json::from_str(&line)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        if let Some(ok) = response.get_mut("ok") {
+            return Ok(Ok(ok.take()));
+        }
+        match response.get("error").and_then(Value::as_str) {
+            Some(error) => Ok(Err(error.to_string())),
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Invalid response",
+            )),
+        }
+    }
+
+    /// Schemas of the values, by name.
+    pub fn list(&mut self) -> io::Result<Result<BTreeMap<String, Schema>, String>> {
+        let list = self.request(&json!({ "cmd": "list" }))?;
+        Ok(list.and_then(|list| serde_json::from_value(list).map_err(|err| err.to_string())))
+    }
+
+    pub fn get(&mut self, name: &str, path: Option<&str>) -> io::Result<Result<Value, String>> {
+        self.request(&json!({ "cmd": "get", "name": name, "path": path }))
+    }
+
+    pub fn set(&mut self, name: &str, path: &str, value: Value) -> io::Result<Result<(), String>> {
+        let set = json!({ "cmd": "set", "name": name, "path": path, "value": value });
+        Ok(self.request(&set)?.map(|_| ()))
+    }
+
+    pub fn press(&mut self, name: &str, button: &str) -> io::Result<Result<(), String>> {
+        let press = json!({ "cmd": "press", "name": name, "button": button });
+        Ok(self.request(&press)?.map(|_| ()))
+    }
+}
@@ -0,0 +1,160 @@
+//!
+//! Serializable description of the UI of a derived type (requires the
+//! `schema` feature).
+//!
+//! The [`Schema`] lists the widgets of each annotated field, with their kind,
+//! label, field type and params (ranges, steps, formats, ...). Containers
+//! (`nested`, `tree`, `child`, ...) hold their widgets as children. It is
+//! built from the `Visit` implementation of the type, so no value nor imgui
+//! context is needed.
+//!
+//! The structure implements serde's `Serialize` and `Deserialize`, so it can
+//! be written as a JSON manifest and used to generate other UIs (such as web
+//! dashboards) from the same annotations.
+//!
+//! # Example
+//!
+//! ```
+//! use imgui_ext::schema::{Param, Schema};
+//!
+//! #[derive(imgui_ext::Gui)]
+//! struct Example {
+//!     #[imgui(slider(min = 0.0, max = 4.0, format = "%.1f"))]
+//!     x: f32,
+//!     #[imgui(checkbox(label = "Turbo"))]
+//!     turbo: bool,
+//! }
+//!
+//! let schema = Schema::of::<Example>();
+//!
+//! assert_eq!("slider", schema.widgets[0].kind);
+//! assert_eq!("f32", schema.widgets[0].ty);
+//! assert_eq!(Some(&Param::Float(4.0)), schema.widgets[0].params.get("max"));
+//! assert_eq!("Turbo", schema.widgets[1].label);
+//! ```
+//!
+//! ## JSON
+//!
+//! ```json
+//! {
+//!   "widgets": [
+//!     {
+//!       "kind": "slider",
+//!       "label": "x",
+//!       "field": "x",
+//!       "type": "f32",
+//!       "params": { "format": "%.1f", "max": 4.0, "min": 0.0 }
+//!     },
+//!     {
+//!       "kind": "checkbox",
+//!       "label": "Turbo",
+//!       "field": "turbo",
+//!       "type": "bool",
+//!       "params": { "label": "Turbo" }
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! [`Schema`]: ./struct.Schema.html
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+
+use crate::visit::{self, ValueMut, Visit, Visitor, Widget};
+
+/// Description of the widgets of a type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Schema {
+    pub widgets: Vec<Node>,
+}
+
+impl Schema {
+    /// Describes the widgets of `T`.
+    pub fn of<T: Visit>() -> Self {
+        let mut builder = Builder {
+            stack: vec![vec![]],
+        };
+        T::describe(&mut builder);
+        Self {
+            widgets: builder.stack.pop().unwrap_or_default(),
+        }
+    }
+}
+
+/// Description of a widget (and of the widgets it contains).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Node {
+    /// Name of the annotation (`"slider"`, `"input"`, `"nested"`, ...)
+    pub kind: String,
+    pub label: String,
+    /// Identifier of the annotated field.
+    pub field: String,
+    /// Type of the annotated field, as written in the struct.
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub params: BTreeMap<String, Param>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<Node>,
+}
+
+/// Value of an annotation param.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Param {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+impl From<&visit::Param<'_>> for Param {
+    fn from(param: &visit::Param) -> Self {
+        match param {
+            visit::Param::Bool(b) => Param::Bool(*b),
+            visit::Param::Int(i) => Param::Int(*i),
+            visit::Param::Float(f) => Param::Float(*f),
+            visit::Param::Str(s) => Param::Str(s.to_string()),
+        }
+    }
+}
+
+impl From<&Widget<'_>> for Node {
+    fn from(widget: &Widget) -> Self {
+        Self {
+            kind: widget.kind.to_string(),
+            label: widget.label.to_string(),
+            field: widget.field.to_string(),
+            ty: widget.ty.to_string(),
+            params: widget
+                .params
+                .iter()
+                .map(|(name, param)| (name.to_string(), param.into()))
+                .collect(),
+            children: Vec::new(),
+        }
+    }
+}
+
+// Builds the tree of nodes. The last element of the stack holds the nodes of
+// the innermost container.
+struct Builder {
+    stack: Vec<Vec<Node>>,
+}
+
+impl Visitor for Builder {
+    fn widget(&mut self, widget: &Widget, _: ValueMut) {
+        self.stack.last_mut().unwrap().push(widget.into());
+    }
+
+    fn begin(&mut self, _: &Widget) {
+        self.stack.push(Vec::new());
+    }
+
+    fn end(&mut self, widget: &Widget) {
+        let children = self.stack.pop().unwrap();
+        let mut node = Node::from(widget);
+        node.children = children;
+        self.stack.last_mut().unwrap().push(node);
+    }
+}
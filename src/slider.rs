@@ -42,6 +42,15 @@ pub struct SliderParams<'a, T> {
 
 pub trait Slider<T> {
     fn build(ui: &imgui::Ui, elem: &mut Self, params: SliderParams<T>) -> bool;
+
+    /// Renders the slider greyed out, without modifying the value.
+    fn build_readonly(ui: &Ui, elem: &Self, params: SliderParams<T>)
+    where
+        Self: Clone,
+    {
+        let mut elem = elem.clone();
+        crate::readonly(ui, || Self::build(ui, &mut elem, params));
+    }
 }
 
 impl<T, S: Slider<T>> Slider<T> for Option<S> {
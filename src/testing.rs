@@ -0,0 +1,652 @@
+//!
+//! Headless harness to test the UIs built with the derive macro (requires the
+//! `testing` feature).
+//!
+//! The [`Harness`] owns an imgui `Context` without a renderer (no window nor
+//! GPU is needed, so the tests can run on CI). Each call to `frame` or
+//! `draw_gui` runs a complete frame and returns a [`Frame`] with:
+//!
+//! * The value returned by the closure (the events in the case of `draw_gui`).
+//! * The widgets emitted by the derived code, which can be queried by label.
+//! * The vertex, index and command counts of the draw lists.
+//!
+//! The contents are drawn inside of a window that covers the whole display.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use imgui_ext::testing::Harness;
+//!
+//! #[derive(imgui_ext::Gui)]
+//! struct Example {
+//!     #[imgui(checkbox(label = "Enabled"))]
+//!     enabled: bool,
+//!     #[imgui(slider(min = 0.0, max = 1.0, catch = "value_changed"))]
+//!     value: f32,
+//! }
+//!
+//! let mut harness = Harness::new();
+//! let mut example = Example { enabled: true, value: 0.5 };
+//!
+//! let frame = harness.draw_gui(&mut example);
+//!
+//! assert_eq!(frame.item("Enabled").unwrap().kind, "checkbox");
+//! assert!(frame.item("value").is_some());
+//! assert!(frame.vertex_count() > 0);
+//! assert!(!frame.events().value_changed());
+//! ```
+//!
+//! ## Simulating input
+//!
+//! A [`Driver`] (returned by `Harness::driver`) locates the widgets by label
+//! in the last frame and injects mouse and keyboard input over the following
+//! frames. The events of every frame are kept, so they can be checked after
+//! the interaction:
+//!
+//! ```ignore
+//! # use imgui_ext::testing::Harness;
+//! #[derive(imgui_ext::Gui)]
+//! struct Example {
+//!     #[imgui(input(label = "Count"), button(label = "Reset", catch = "reset"))]
+//!     count: i32,
+//! }
+//!
+//! let mut harness = Harness::new();
+//! let mut example = Example { count: 0 };
+//!
+//! let mut driver = harness.driver(&mut example);
+//! driver.type_text("Count", "42");
+//! assert_eq!(42, driver.value().count);
+//! assert!(driver.fired(|events| events.count()));
+//!
+//! driver.clear_events();
+//! driver.click("Reset");
+//! assert!(driver.fired(|events| events.reset()));
+//! ```
+//!
+//! ## Snapshots
+//!
+//! `Frame::snapshot` returns the tree of widgets of a frame (kind, label,
+//! value, annotation and nesting), which can be compared with a `.snap` file
+//! using [`assert_snapshot`]. Changes to the `#[imgui]` annotations then show
+//! up as readable diffs of the `.snap` files:
+//!
+//! ```ignore
+//! # use imgui_ext::testing::{assert_snapshot, Harness};
+//! let frame = harness.draw_gui(&mut example);
+//! assert_snapshot("tests/snapshots/example.snap", &frame.snapshot());
+//! ```
+//!
+//! [`Harness`]: ./struct.Harness.html
+//! [`Frame`]: ./struct.Frame.html
+//! [`Driver`]: ./struct.Driver.html
+//! [`assert_snapshot`]: ./fn.assert_snapshot.html
+use imgui::{im_str, sys, Condition, Context, ImString, Key, Ui, Window};
+
+use std::cell::RefCell;
+use std::fmt;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::Gui;
+
+thread_local! {
+    // Widgets of the frame being run by a harness (if any).
+    static RECORDING: RefCell<Option<Recording>> = RefCell::new(None);
+}
+
+#[derive(Default)]
+struct Recording {
+    items: Vec<Item>,
+    depth: usize,
+    params: Option<&'static str>,
+}
+
+/// Widget emitted by the derived code during a frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Item {
+    /// Name of the annotation (`"checkbox"`, `"slider"`, `"button"`, ...)
+    pub kind: &'static str,
+    /// Label of the widget, including the `##` suffix (if any).
+    pub label: String,
+    /// Value of the field after the widget was drawn, formatted with `Debug`.
+    /// `None` if the type doesn't implement `Debug`, and for buttons and
+    /// containers.
+    pub value: Option<String>,
+    /// Source of the `#[imgui(...)]` annotation. Only set on the first widget
+    /// of each field.
+    pub params: Option<&'static str>,
+    /// Number of containers (`nested`, `tree`, `child`, ...) around the widget.
+    pub depth: usize,
+    /// Upper-left corner of the widget, in screen coordinates (zero for
+    /// containers).
+    pub min: [f32; 2],
+    /// Lower-right corner of the widget, in screen coordinates (zero for
+    /// containers).
+    pub max: [f32; 2],
+    /// The mouse was over the widget.
+    pub hovered: bool,
+    /// The widget was active (being clicked, dragged or edited).
+    pub active: bool,
+    /// The widget modified its value.
+    pub edited: bool,
+}
+
+impl Item {
+    /// Visible part of the label (the text before the `##`).
+    pub fn name(&self) -> &str {
+        self.label.split("##").next().unwrap_or("")
+    }
+
+    /// Center of the widget, in screen coordinates.
+    pub fn center(&self) -> [f32; 2] {
+        [
+            (self.min[0] + self.max[0]) / 2.0,
+            (self.min[1] + self.max[1]) / 2.0,
+        ]
+    }
+}
+
+/// Records the last widget, when a harness frame is running.
+pub(crate) fn record(ui: &Ui, kind: &'static str, label: &str, value: &dyn Fn() -> Option<String>) {
+    RECORDING.with(|rec| {
+        if let Some(rec) = rec.borrow_mut().as_mut() {
+            let (min, max) = unsafe { (sys::igGetItemRectMin(), sys::igGetItemRectMax()) };
+            rec.items.push(Item {
+                kind,
+                label: label.to_string(),
+                value: value(),
+                params: rec.params.take(),
+                depth: rec.depth,
+                min: [min.x, min.y],
+                max: [max.x, max.y],
+                hovered: ui.is_item_hovered(),
+                active: ui.is_item_active(),
+                edited: unsafe { sys::igIsItemEdited() },
+            });
+        }
+    });
+}
+
+/// Records a container. The following widgets are its children until `end`.
+pub(crate) fn begin(kind: &'static str, label: &str) {
+    RECORDING.with(|rec| {
+        if let Some(rec) = rec.borrow_mut().as_mut() {
+            rec.items.push(Item {
+                kind,
+                label: label.to_string(),
+                value: None,
+                params: rec.params.take(),
+                depth: rec.depth,
+                min: [0.0, 0.0],
+                max: [0.0, 0.0],
+                hovered: false,
+                active: false,
+                edited: false,
+            });
+            rec.depth += 1;
+        }
+    });
+}
+
+pub(crate) fn end() {
+    RECORDING.with(|rec| {
+        if let Some(rec) = rec.borrow_mut().as_mut() {
+            rec.depth -= 1;
+        }
+    });
+}
+
+/// Sets the annotation source of the next widget.
+pub(crate) fn params(params: Option<&'static str>) {
+    RECORDING.with(|rec| {
+        if let Some(rec) = rec.borrow_mut().as_mut() {
+            rec.params = params;
+        }
+    });
+}
+
+/// Output of a single frame.
+pub struct Frame<R> {
+    output: R,
+    items: Vec<Item>,
+    vertices: usize,
+    indices: usize,
+    commands: usize,
+}
+
+impl<R> Frame<R> {
+    /// Value returned by the frame closure.
+    pub fn output(&self) -> &R {
+        &self.output
+    }
+
+    pub fn into_output(self) -> R {
+        self.output
+    }
+
+    /// Same as `output`. Reads better on frames returned by `draw_gui`.
+    pub fn events(&self) -> &R {
+        &self.output
+    }
+
+    /// Widgets emitted during the frame, in order.
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    /// Returns the first widget with the given label. The label is compared
+    /// with and without the `##` suffix.
+    pub fn item(&self, label: &str) -> Option<&Item> {
+        self.items
+            .iter()
+            .find(|item| item.label == label || item.name() == label)
+    }
+
+    /// Returns the widgets of the given kind (`"checkbox"`, `"input"`, ...)
+    pub fn items_of(&self, kind: &str) -> Vec<&Item> {
+        self.items.iter().filter(|item| item.kind == kind).collect()
+    }
+
+    /// Tree of the widgets emitted during the frame.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot::new(&self.items)
+    }
+
+    /// Total number of vertices of the draw lists.
+    pub fn vertex_count(&self) -> usize {
+        self.vertices
+    }
+
+    /// Total number of indices of the draw lists.
+    pub fn index_count(&self) -> usize {
+        self.indices
+    }
+
+    /// Total number of draw commands of the draw lists.
+    pub fn command_count(&self) -> usize {
+        self.commands
+    }
+}
+
+/// Widget of a [`Snapshot`], with the widgets it contains.
+///
+/// [`Snapshot`]: ./struct.Snapshot.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    pub kind: &'static str,
+    pub label: String,
+    pub value: Option<String>,
+    pub params: Option<&'static str>,
+    pub children: Vec<Node>,
+}
+
+/// Tree of the widgets emitted by the derived code during a frame.
+///
+/// It is serialized as text (one widget per line, children indented) so it
+/// can be stored in a `.snap` file and compared with [`assert_snapshot`]:
+///
+/// ```text
+/// checkbox "Enabled" = true | checkbox(label = "Enabled")
+/// nested "nested" | nested
+///   slider "x" = 0.5 | slider(min = 0.0, max = 1.0)
+/// ```
+///
+/// [`assert_snapshot`]: ./fn.assert_snapshot.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub nodes: Vec<Node>,
+}
+
+impl Snapshot {
+    fn new(items: &[Item]) -> Self {
+        fn children(
+            items: &mut std::iter::Peekable<std::slice::Iter<Item>>,
+            depth: usize,
+        ) -> Vec<Node> {
+            let mut nodes = Vec::new();
+            while let Some(item) = items.peek() {
+                if item.depth < depth {
+                    break;
+                }
+                let item = items.next().unwrap();
+                nodes.push(Node {
+                    kind: item.kind,
+                    label: item.label.clone(),
+                    value: item.value.clone(),
+                    params: item.params,
+                    children: children(items, depth + 1),
+                });
+            }
+            nodes
+        }
+        Self {
+            nodes: children(&mut items.iter().peekable(), 0),
+        }
+    }
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn write(f: &mut fmt::Formatter, nodes: &[Node], depth: usize) -> fmt::Result {
+            for node in nodes {
+                write!(
+                    f,
+                    "{:indent$}{} {:?}",
+                    "",
+                    node.kind,
+                    node.label,
+                    indent = depth * 2
+                )?;
+                if let Some(value) = &node.value {
+                    write!(f, " = {}", value)?;
+                }
+                if let Some(params) = node.params {
+                    write!(f, " | {}", params)?;
+                }
+                writeln!(f)?;
+                write(f, &node.children, depth + 1)?;
+            }
+            Ok(())
+        }
+        write(f, &self.nodes, 0)
+    }
+}
+
+/// Compares the snapshot with the contents of the `.snap` file at `path`,
+/// and panics with a line diff if they differ.
+///
+/// The file is written (and the assertion passes) if it doesn't exist, or if
+/// the `IMGUI_EXT_UPDATE_SNAPSHOTS` environment variable is set.
+pub fn assert_snapshot<P: AsRef<Path>>(path: P, snapshot: &Snapshot) {
+    let path = path.as_ref();
+    let actual = snapshot.to_string();
+
+    if !path.exists() || std::env::var_os("IMGUI_EXT_UPDATE_SNAPSHOTS").is_some() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("Error creating snapshot directory.");
+        }
+        std::fs::write(path, &actual).expect("Error writing snapshot.");
+        return;
+    }
+
+    let expected = std::fs::read_to_string(path).expect("Error reading snapshot.");
+    if expected != actual {
+        panic!(
+            "Snapshot {} doesn't match (- expected, + actual):\n{}",
+            path.display(),
+            diff(&expected, &actual)
+        );
+    }
+}
+
+// Line diff of two texts (longest common subsequence).
+fn diff(expected: &str, actual: &str) -> String {
+    let a: Vec<_> = expected.lines().collect();
+    let b: Vec<_> = actual.lines().collect();
+
+    // lcs[i][j] = length of the LCS of a[i..] and b[j..]
+    let mut lcs = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            out.push_str(&format!("  {}\n", a[i]));
+            i += 1;
+            j += 1;
+        } else if j < b.len() && (i == a.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            out.push_str(&format!("+ {}\n", b[j]));
+            j += 1;
+        } else {
+            out.push_str(&format!("- {}\n", a[i]));
+            i += 1;
+        }
+    }
+    out
+}
+
+// Keys that can be pressed by a Driver.
+const KEYS: &[Key] = &[
+    Key::Tab,
+    Key::LeftArrow,
+    Key::RightArrow,
+    Key::UpArrow,
+    Key::DownArrow,
+    Key::Home,
+    Key::End,
+    Key::Delete,
+    Key::Backspace,
+    Key::Enter,
+    Key::Escape,
+    Key::A,
+];
+
+// imgui supports a single context per process. The harness holds the lock for
+// its whole lifetime, so the tests (which run in parallel) take turns.
+static CONTEXT: Mutex<()> = Mutex::new(());
+
+/// Runs imgui frames without a renderer.
+///
+/// imgui supports a single context at a time, so creating a harness blocks
+/// until any other harness has been dropped.
+pub struct Harness {
+    // dropped before the lock is released
+    ctx: Context,
+    size: [f32; 2],
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Harness {
+    /// Creates a harness with a display of 800x600.
+    pub fn new() -> Self {
+        Self::with_size([800.0, 600.0])
+    }
+
+    pub fn with_size(size: [f32; 2]) -> Self {
+        // the lock is poisoned by a test that panics with a harness alive
+        let _lock = CONTEXT.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut ctx = Context::create();
+        ctx.set_ini_filename(None::<ImString>);
+        ctx.io_mut().display_size = size;
+        ctx.io_mut().delta_time = 1.0 / 60.0;
+        // identity key map, so the Driver can press keys by index
+        for &key in KEYS {
+            ctx.io_mut()[key] = key as _;
+        }
+        // the font atlas must be built before the first frame.
+        // The texture itself is discarded, as nothing is rendered.
+        ctx.fonts().build_rgba32_texture();
+        Self { ctx, size, _lock }
+    }
+
+    /// The imgui context, to modify the `Io` or the style before a frame.
+    pub fn context(&mut self) -> &mut Context {
+        &mut self.ctx
+    }
+
+    /// Runs a frame, building the UI inside of `f`.
+    pub fn frame<R, F: FnOnce(&Ui) -> R>(&mut self, f: F) -> Frame<R> {
+        RECORDING.with(|rec| *rec.borrow_mut() = Some(Recording::default()));
+
+        let size = self.size;
+        let ui = self.ctx.frame();
+        let mut output = None;
+        Window::new(im_str!("imgui-ext testing"))
+            .position([0.0, 0.0], Condition::Always)
+            .size(size, Condition::Always)
+            .build(&ui, || output = Some(f(&ui)));
+
+        let draw_data = ui.render();
+        let vertices = draw_data.total_vtx_count as usize;
+        let indices = draw_data.total_idx_count as usize;
+        let commands = draw_data
+            .draw_lists()
+            .map(|list| list.commands().count())
+            .sum();
+
+        let items = RECORDING.with(|rec| rec.borrow_mut().take().unwrap_or_default().items);
+
+        Frame {
+            output: output.expect("The harness window was not drawn."),
+            items,
+            vertices,
+            indices,
+            commands,
+        }
+    }
+
+    /// Runs a frame drawing `ext`.
+    pub fn draw_gui<T: Gui>(&mut self, ext: &mut T) -> Frame<T::Events> {
+        self.frame(|ui| T::draw_gui(ui, ext))
+    }
+
+    /// Runs a frame drawing `ext` in read-only mode.
+    pub fn draw_gui_readonly<T: Gui>(&mut self, ext: &T) -> Frame<T::Events> {
+        self.frame(|ui| T::draw_gui_readonly(ui, ext))
+    }
+
+    /// Returns a driver to interact with the UI of `ext`.
+    pub fn driver<'a, T: Gui>(&'a mut self, ext: &'a mut T) -> Driver<'a, T> {
+        Driver {
+            harness: self,
+            ext,
+            frames: Vec::new(),
+        }
+    }
+}
+
+/// Injects mouse and keyboard input into the frames of a harness.
+///
+/// Every method runs one or more frames drawing the value with `draw_gui`.
+/// Widgets are located in the last frame (a frame without input is run first
+/// if there isn't one). Panics if the widget can't be found.
+pub struct Driver<'a, T: Gui> {
+    harness: &'a mut Harness,
+    ext: &'a mut T,
+    frames: Vec<Frame<T::Events>>,
+}
+
+impl<T: Gui> Driver<'_, T> {
+    /// The value being edited.
+    pub fn value(&self) -> &T {
+        &*self.ext
+    }
+
+    /// Runs a single frame without any new input.
+    pub fn step(&mut self) -> &Frame<T::Events> {
+        let frame = self.harness.draw_gui(self.ext);
+        self.frames.push(frame);
+        self.frames.last().unwrap()
+    }
+
+    /// Last frame run by the driver.
+    pub fn last_frame(&self) -> Option<&Frame<T::Events>> {
+        self.frames.last()
+    }
+
+    /// Every frame run by the driver (since the last call to
+    /// `clear_events`), in order.
+    pub fn frames(&self) -> &[Frame<T::Events>] {
+        &self.frames
+    }
+
+    /// Returns `true` if `event` is true in any of the frames.
+    pub fn fired<F: Fn(&T::Events) -> bool>(&self, event: F) -> bool {
+        self.frames.iter().any(|frame| event(frame.events()))
+    }
+
+    /// Forgets the previous frames.
+    pub fn clear_events(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Moves the mouse over the widget.
+    pub fn hover(&mut self, label: &str) -> &mut Self {
+        let center = self.find(label).center();
+        self.input(|io| io.mouse_pos = center);
+        self
+    }
+
+    /// Presses and releases the left button over the widget.
+    pub fn click(&mut self, label: &str) -> &mut Self {
+        self.hover(label);
+        self.input(|io| io.mouse_down[0] = true);
+        self.input(|io| io.mouse_down[0] = false);
+        self
+    }
+
+    /// Presses the left button over the widget, moves the mouse by `delta`
+    /// and releases the button.
+    pub fn drag(&mut self, label: &str, delta: [f32; 2]) -> &mut Self {
+        let [x, y] = self.find(label).center();
+        self.hover(label);
+        self.input(|io| io.mouse_down[0] = true);
+        // move in a few steps, so the drag threshold is crossed first
+        for i in 1..=4 {
+            let t = i as f32 / 4.0;
+            self.input(|io| io.mouse_pos = [x + delta[0] * t, y + delta[1] * t]);
+        }
+        self.input(|io| io.mouse_down[0] = false);
+        self
+    }
+
+    /// Clicks the widget, replaces its text with `text` and presses `Enter`.
+    pub fn type_text(&mut self, label: &str, text: &str) -> &mut Self {
+        self.click(label);
+        self.input(|io| {
+            io.key_ctrl = true;
+            io.keys_down[Key::A as usize] = true;
+        });
+        self.input(|io| {
+            io.key_ctrl = false;
+            io.keys_down[Key::A as usize] = false;
+        });
+        for c in text.chars() {
+            self.input(|io| io.add_input_character(c));
+        }
+        self.press(Key::Enter)
+    }
+
+    /// Presses and releases a key.
+    ///
+    /// Only the keys used to navigate and edit text are mapped.
+    pub fn press(&mut self, key: Key) -> &mut Self {
+        assert!(KEYS.contains(&key), "Key {:?} is not mapped.", key);
+        self.input(|io| io.keys_down[key as usize] = true);
+        self.input(|io| io.keys_down[key as usize] = false);
+        self
+    }
+
+    // Locates a widget in the last frame.
+    fn find(&mut self, label: &str) -> Item {
+        if self.frames.is_empty() {
+            self.step();
+        }
+        match self.frames.last().unwrap().item(label) {
+            Some(item) => item.clone(),
+            None => panic!("Widget {:?} not found.", label),
+        }
+    }
+
+    // Modifies the Io and runs a frame.
+    fn input<F: FnOnce(&mut imgui::Io)>(&mut self, f: F) {
+        f(self.harness.ctx.io_mut());
+        self.step();
+    }
+}
@@ -0,0 +1,113 @@
+//!
+//! Params accepted by the annotations that report changes (`checkbox`,
+//! `input`, `slider`, `drag`, `color(...)` and `menu_item`), applied right
+//! after the widget modifies the value of the field:
+//!
+//! * `clamp(min, max)` clamps the value into the given range. Arrays are
+//!   clamped element-wise. The type of the field (or the value returned by the
+//!   `map` function) must implement the [`Clamp`] trait.
+//! * `validate = "..."` path to a function taking a reference to the field and
+//!   returning `Result<(), String>`. Changes to a value that is not valid are
+//!   kept, but they are not reported by the events, and the error is shown in
+//!   red below the widget until the value is valid again.
+//! * `on_change = "..."` method of the struct, called when the value changes.
+//!   It is not called while the value is not valid. See the [callback
+//!   docs](../callback/index.html) for the accepted functions.
+//!
+//! Note that `drag(min = .., max = ..)` only limits the range of the drag
+//! itself. Values typed into the widget (`Ctrl+Click`) can still be out of
+//! range, unless the annotation uses `clamp` too.
+//!
+//! ## Example
+//!
+//! ```
+//! use imgui::ImString;
+//!
+//! #[derive(imgui_ext::Gui)]
+//! struct Server {
+//!     #[imgui(input(clamp(1, 65535), on_change = "restart"))]
+//!     port: i32,
+//!     #[imgui(input(validate = "not_empty", on_change = "restart"))]
+//!     host: ImString,
+//!     #[imgui(drag(speed = 0.01, clamp(0.0, 1.0)))]
+//!     load: [f32; 2],
+//! }
+//!
+//! impl Server {
+//!     fn restart(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! fn not_empty(host: &ImString) -> Result<(), String> {
+//!     if host.to_str().is_empty() {
+//!         Err("The host can't be empty.".to_string())
+//!     } else {
+//!         Ok(())
+//!     }
+//! }
+//! ```
+use imgui::{sys, ImStr, Ui};
+
+/// Trait for types that can be clamped by the `clamp(min, max)` param.
+pub trait Clamp {
+    /// Type of the bounds. The bounds of the annotation are cast to this type.
+    type Elem;
+    fn clamp(elem: &mut Self, min: Self::Elem, max: Self::Elem);
+}
+
+impl<C: Clamp> Clamp for Option<C> {
+    type Elem = C::Elem;
+    fn clamp(elem: &mut Self, min: Self::Elem, max: Self::Elem) {
+        if let Some(ref mut elem) = elem {
+            C::clamp(elem, min, max);
+        }
+    }
+}
+
+impl<C: Clamp> Clamp for Box<C> {
+    type Elem = C::Elem;
+    #[inline]
+    fn clamp(elem: &mut Self, min: Self::Elem, max: Self::Elem) {
+        C::clamp(elem, min, max);
+    }
+}
+
+imgui_clamp_scalar! { f32, f64, u32, i32 }
+imgui_clamp_array! { 1, 2, 3, 4, 5, 6, 7, 8 }
+
+/// Shows the error returned by a `validate` function.
+pub fn error(ui: &Ui, error: &str) {
+    ui.text_colored([1.0, 0.4, 0.4, 1.0], error);
+}
+
+// Called by the derived code after a widget with a `validate` function. The
+// value is only validated when the widget changed it, or while the last
+// validation failed (the error is shown until the value is valid again).
+// Whether the field is invalid is kept in the state storage of the window,
+// under the given `id`. Returns `false` while the value is not valid.
+#[doc(hidden)]
+pub fn __validate<F>(ui: &Ui, id: &ImStr, changed: bool, validate: F) -> bool
+where
+    F: FnOnce() -> Result<(), String>,
+{
+    unsafe {
+        let storage = sys::igGetStateStorage();
+        let id = sys::igGetIDStr(id.as_ptr());
+        let invalid = sys::ImGuiStorage_GetBool(storage, id, false);
+        if !changed && !invalid {
+            return true;
+        }
+        match validate() {
+            Ok(()) => {
+                sys::ImGuiStorage_SetBool(storage, id, false);
+                true
+            }
+            Err(e) => {
+                sys::ImGuiStorage_SetBool(storage, id, true);
+                error(ui, &e);
+                false
+            }
+        }
+    }
+}
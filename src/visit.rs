@@ -0,0 +1,212 @@
+//!
+//! Besides `Gui`, the derive macro implements the [`Visit`] trait, which
+//! walks the annotated fields without calling imgui. The [`Visitor`] receives
+//! the kind, label and params of each widget, and a mutable reference to the
+//! value, so the same `#[imgui]` annotations can drive other frontends
+//! (console, web, tests, ...)
+//!
+//! Containers (`nested`, `menu`, `tree`, `child`, `popup`, `modal` and
+//! `vars`) call `begin` and `end` around the widgets they contain.
+//!
+//! Nested fields (`nested` and `menu` without a `map`) are visited through the
+//! `Visit` implementation of their type, so the derived implementation is
+//! bounded on it: a struct nesting a type with a hand-written `Gui`
+//! implementation (or a generic `T: Gui`) still derives `Gui`, but it only
+//! implements `Visit` when the nested types do. Mapped fields must map to a
+//! type implementing `Visit`.
+//!
+//! # Example
+//!
+//! ```
+//! use imgui_ext::visit::{Param, ValueMut, Visitor, Widget};
+//! use imgui_ext::Visit;
+//!
+//! #[derive(imgui_ext::Gui)]
+//! struct Example {
+//!     #[imgui(slider(min = "-1.0", max = 1.0))]
+//!     x: f32,
+//!     #[imgui(checkbox(label = "Turbo"))]
+//!     turbo: bool,
+//! }
+//!
+//! // Sets every field to the max value of its widget.
+//! struct Maximize;
+//!
+//! impl Visitor for Maximize {
+//!     fn widget(&mut self, widget: &Widget, value: ValueMut) {
+//!         match (widget.param("max"), value) {
+//!             (Some(Param::Float(max)), ValueMut::F32(x)) => *x = *max as f32,
+//!             (_, ValueMut::Bool(b)) => *b = true,
+//!             _ => {}
+//!         }
+//!     }
+//! }
+//!
+//! let mut example = Example { x: 0.0, turbo: false };
+//! Visit::visit(&mut example, &mut Maximize);
+//!
+//! assert_eq!(1.0, example.x);
+//! assert!(example.turbo);
+//! ```
+//!
+//! [`Visit`]: ../trait.Visit.html
+//! [`Visitor`]: ./trait.Visitor.html
+use imgui::ImString;
+
+use std::cell::Cell;
+
+/// Trait implemented by `#[derive(imgui_ext::Gui)]`.
+pub trait Visit {
+    fn visit<V: Visitor>(ext: &mut Self, visitor: &mut V);
+
+    /// Walks the widgets of the type, without a value. Every widget is passed
+    /// to the visitor with `ValueMut::None`.
+    fn describe<V: Visitor>(visitor: &mut V);
+}
+
+impl<T: Visit> Visit for Option<T> {
+    fn visit<V: Visitor>(ext: &mut Self, visitor: &mut V) {
+        if let Some(ext) = ext {
+            T::visit(ext, visitor)
+        }
+    }
+
+    #[inline]
+    fn describe<V: Visitor>(visitor: &mut V) {
+        T::describe(visitor)
+    }
+}
+
+impl<T: Visit> Visit for Box<T> {
+    #[inline]
+    fn visit<V: Visitor>(ext: &mut Self, visitor: &mut V) {
+        T::visit(ext.as_mut(), visitor)
+    }
+
+    #[inline]
+    fn describe<V: Visitor>(visitor: &mut V) {
+        T::describe(visitor)
+    }
+}
+
+// Used by the derived `describe` on nested fields with a `map` param, to get
+// the type returned by the map function.
+#[doc(hidden)]
+pub fn describe_mapped<F, T: Visit, V: Visitor>(_: fn(&mut F) -> &mut T, visitor: &mut V) {
+    T::describe(visitor)
+}
+
+/// Receives the widgets of a type implementing `Visit`.
+pub trait Visitor {
+    /// Called once per widget, in the order of the annotations.
+    fn widget(&mut self, widget: &Widget, value: ValueMut);
+
+    /// Called before the widgets of a container.
+    fn begin(&mut self, _widget: &Widget) {}
+
+    /// Called after the widgets of a container.
+    fn end(&mut self, _widget: &Widget) {}
+}
+
+/// Description of a widget, taken from its annotation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Widget<'a> {
+    /// Name of the annotation (`"slider"`, `"input"`, `"nested"`, ...)
+    pub kind: &'a str,
+    /// Label of the widget. Defaults to the field identifier.
+    pub label: &'a str,
+    /// Identifier of the annotated field.
+    pub field: &'a str,
+    /// Type of the annotated field, as written in the struct.
+    pub ty: &'a str,
+    /// Params of the annotation (`min`, `max`, `step`, `flags`, ...)
+    pub params: &'a [(&'a str, Param<'a>)],
+}
+
+impl<'a> Widget<'a> {
+    /// Returns the param with the given name.
+    pub fn param(&self, name: &str) -> Option<&Param<'a>> {
+        self.params
+            .iter()
+            .find(|(param, _)| *param == name)
+            .map(|(_, value)| value)
+    }
+}
+
+/// Value of an annotation param. Numbers written as strings (such as
+/// `min = "-1.0"`) are parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Param<'a> {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(&'a str),
+}
+
+/// Mutable reference to the value of a widget.
+///
+/// Types other than the ones below are passed as `Other`.
+#[derive(Debug)]
+pub enum ValueMut<'a> {
+    Bool(&'a mut bool),
+    I32(&'a mut i32),
+    F32(&'a mut f32),
+    I32Array(&'a mut [i32]),
+    F32Array(&'a mut [f32]),
+    ImString(&'a mut ImString),
+    Other,
+    /// Widgets without a value (separators, text, buttons, ...)
+    None,
+}
+
+// Used by the derived code to turn the fields into a `ValueMut`
+// (autoref specialization).
+#[doc(hidden)]
+pub struct Value<'a, T>(Cell<Option<&'a mut T>>);
+
+impl<'a, T> Value<'a, T> {
+    pub fn new(value: &'a mut T) -> Self {
+        Value(Cell::new(Some(value)))
+    }
+}
+
+#[doc(hidden)]
+pub trait IntoValue<'a> {
+    fn value(&self) -> ValueMut<'a>;
+}
+
+#[doc(hidden)]
+pub trait NoValue<'a> {
+    fn value(&self) -> ValueMut<'a>;
+}
+
+impl<'a, T> NoValue<'a> for Value<'a, T> {
+    fn value(&self) -> ValueMut<'a> {
+        ValueMut::Other
+    }
+}
+
+macro_rules! into_value {
+    ($( $ty:ty => $variant:ident ,)+) => {$(
+        impl<'a> IntoValue<'a> for &Value<'a, $ty> {
+            fn value(&self) -> ValueMut<'a> {
+                ValueMut::$variant(self.0.take().unwrap())
+            }
+        }
+    )+}
+}
+
+into_value! {
+    bool => Bool,
+    i32 => I32,
+    f32 => F32,
+    [i32; 1] => I32Array,
+    [i32; 2] => I32Array,
+    [i32; 3] => I32Array,
+    [i32; 4] => I32Array,
+    [f32; 1] => F32Array,
+    [f32; 2] => F32Array,
+    [f32; 3] => F32Array,
+    [f32; 4] => F32Array,
+    ImString => ImString,
+}
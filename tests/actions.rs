@@ -0,0 +1,76 @@
+#[test]
+fn actions() {
+    #[derive(imgui_ext::Gui)]
+    #[imgui(actions(
+        increment = "Increment",
+        reset(label = "Reset", confirm = "Are you sure?"),
+        clear
+    ))]
+    struct Test {
+        #[imgui(input(catch = "count_changed"))]
+        count: i32,
+    }
+
+    impl Test {
+        fn increment(&mut self) {
+            self.count += 1;
+        }
+
+        fn reset(&mut self) {
+            self.count = 0;
+        }
+
+        fn clear(&mut self) {
+            self.count = -1;
+        }
+    }
+
+    fn _events(ui: &imgui::Ui, test: &mut Test) -> bool {
+        use imgui_ext::UiExt;
+        let events = ui.draw_gui(test);
+        events.count_changed() || events.increment() || events.reset() || events.clear()
+    }
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn actions_driver() {
+    use imgui_ext::testing::Harness;
+
+    #[derive(imgui_ext::Gui)]
+    #[imgui(actions(
+        increment = "Increment",
+        reset(label = "Reset", confirm = "Are you sure?")
+    ))]
+    struct Test {
+        #[imgui(input(label = "Count"))]
+        count: i32,
+    }
+
+    impl Test {
+        fn increment(&mut self) {
+            self.count += 1;
+        }
+
+        fn reset(&mut self) {
+            self.count = 0;
+        }
+    }
+
+    let mut harness = Harness::new();
+    let mut test = Test { count: 3 };
+
+    let mut driver = harness.driver(&mut test);
+    driver.click("Increment");
+    assert_eq!(4, driver.value().count);
+    assert!(driver.fired(|events| events.increment()));
+
+    // the action is only called (and reported) once confirmed
+    driver.click("Reset");
+    assert_eq!(4, driver.value().count);
+    assert!(!driver.fired(|events| events.reset()));
+    driver.step();
+    driver.click("OK");
+    assert_eq!(0, driver.value().count);
+    assert!(driver.fired(|events| events.reset()));
+}
@@ -0,0 +1,67 @@
+use imgui_ext::baseline::{self, Diff};
+
+#[derive(imgui_ext::Gui, Clone, Default)]
+struct Test {
+    #[imgui(slider(min = 0.0, max = 4.0))]
+    a: f32,
+    #[imgui(nested)]
+    b: Nested,
+    #[imgui(nested)]
+    c: Option<Nested>,
+    // not PartialEq
+    #[imgui(button(label = "D"))]
+    d: NoEq,
+}
+
+#[derive(imgui_ext::Gui, Clone, Default, PartialEq)]
+struct Nested {
+    #[imgui(checkbox)]
+    e: bool,
+    #[imgui(input)]
+    f: i32,
+}
+
+#[derive(Clone, Default)]
+struct NoEq;
+
+#[test]
+fn baseline() {
+    let base = Test::default();
+    let mut test = Test::default();
+    assert!(baseline::dirty(&test, &base).is_empty());
+
+    test.a = 1.0;
+    test.b.f = 2;
+    test.c = Some(Nested::default());
+    assert_eq!(vec!["a", "b.f", "c"], baseline::dirty(&test, &base));
+
+    assert!(Diff::revert(&mut test, &base, "b.f"));
+    assert!(Diff::revert(&mut test, &base, "c"));
+    assert!(!Diff::revert(&mut test, &base, "missing"));
+    assert_eq!(vec!["a"], baseline::dirty(&test, &base));
+    assert_eq!(0, test.b.f);
+    assert!(test.c.is_none());
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn baseline_draw() {
+    use imgui_ext::testing::Harness;
+    use imgui_ext::UiExt;
+
+    let base = Test::default();
+    let mut test = Test::default();
+    test.b.e = true;
+
+    let mut harness = Harness::new();
+    let frame = harness.frame(|ui| ui.draw_gui_with_baseline(&mut test, &base));
+    assert_eq!(&["b.e"], frame.output().dirty());
+    assert!(frame.output().is_dirty("b.e"));
+    assert_eq!(&["b.e"], frame.output().events.dirty_fields());
+    assert!(frame.output().events.is_dirty("b.e"));
+    assert!(frame.output().reverted().is_empty());
+
+    // empty without a baseline
+    let frame = harness.draw_gui(&mut test);
+    assert!(frame.events().dirty_fields().is_empty());
+}
@@ -0,0 +1,104 @@
+#[test]
+fn callback() {
+    #[derive(imgui_ext::Gui)]
+    struct Test {
+        #[imgui(slider(min = 0.0, max = 1.0, on_change = "Self::set_a"))]
+        a: f32,
+        #[imgui(checkbox(on_change = "Self::changed"), menu_item(on_change = "changed"))]
+        b: bool,
+        #[imgui(
+            input(on_change = "Test::set_c", validate = "positive"),
+            button(label = "Reset", on_click = "Self::reset", catch = "reset"),
+            button(label = "Count", on_click = "changed")
+        )]
+        c: [i32; 2],
+        changes: usize,
+    }
+
+    impl Test {
+        fn set_a(&mut self, a: f32) {
+            self.b = a > 0.5;
+        }
+
+        fn set_c(&mut self, c: [i32; 2]) {
+            self.a = (c[0] + c[1]) as f32;
+        }
+
+        fn changed(&mut self) {
+            self.changes += 1;
+        }
+
+        fn reset(&mut self) {
+            self.c = [0, 0];
+        }
+    }
+
+    fn positive(c: &[i32; 2]) -> Result<(), String> {
+        if c[0] < 0 || c[1] < 0 {
+            Err("Negative".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn _events(ui: &imgui::Ui, test: &mut Test) -> bool {
+        use imgui_ext::UiExt;
+        let events = ui.draw_gui(test);
+        events.a() || events.b() || events.c() || events.reset()
+    }
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn callback_driver() {
+    use imgui_ext::testing::Harness;
+
+    #[derive(imgui_ext::Gui)]
+    struct Test {
+        #[imgui(checkbox(label = "Enabled", on_change = "changed"))]
+        enabled: bool,
+        #[imgui(
+            input(label = "Count", on_change = "Self::set_count"),
+            button(label = "Reset", on_click = "Self::reset", catch = "reset")
+        )]
+        count: i32,
+        changes: usize,
+        last: Option<i32>,
+    }
+
+    impl Test {
+        fn changed(&mut self) {
+            self.changes += 1;
+        }
+
+        fn set_count(&mut self, count: i32) {
+            self.last = Some(count);
+        }
+
+        fn reset(&mut self) {
+            self.count = 0;
+        }
+    }
+
+    let mut harness = Harness::new();
+    let mut test = Test {
+        enabled: false,
+        count: 3,
+        changes: 0,
+        last: None,
+    };
+
+    let mut driver = harness.driver(&mut test);
+    driver.click("Enabled");
+    assert!(driver.value().enabled);
+    assert_eq!(1, driver.value().changes);
+    assert!(driver.fired(|events| events.enabled()));
+
+    driver.type_text("Count", "42");
+    assert_eq!(Some(42), driver.value().last);
+    assert!(driver.fired(|events| events.count()));
+
+    driver.click("Reset");
+    assert_eq!(0, driver.value().count);
+    assert!(driver.fired(|events| events.reset()));
+}
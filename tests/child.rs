@@ -0,0 +1,26 @@
+#[test]
+fn child() {
+    #[derive(imgui_ext::Gui)]
+    struct Test {
+        #[imgui(child(content(checkbox)))]
+        a: bool,
+        #[imgui(child(label = "b##child", border = true, size = "size", content(nested)))]
+        b: Nested,
+        #[imgui(child(flags = "flags", content(input(catch = "c_input"), drag)))]
+        c: f32,
+    }
+
+    #[derive(imgui_ext::Gui)]
+    struct Nested {
+        #[imgui(checkbox)]
+        d: bool,
+    }
+
+    fn size() -> [f32; 2] {
+        [0.0, 64.0]
+    }
+
+    fn flags() -> imgui::ImGuiWindowFlags {
+        imgui::ImGuiWindowFlags::AlwaysVerticalScrollbar
+    }
+}
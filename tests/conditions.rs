@@ -0,0 +1,31 @@
+#[test]
+fn conditions() {
+    #[derive(imgui_ext::Gui)]
+    struct Test {
+        #[imgui(checkbox)]
+        a: bool,
+        #[imgui(input(visible_if = "a"), slider(min = 0.0, max = 1.0, enabled_if = "a"))]
+        b: f32,
+        #[imgui(drag(visible_if = "Self::visible", enabled_if = "Test::enabled"))]
+        c: [f32; 2],
+        #[imgui(
+            checkbox(enabled_if = "a", disabled_if = "locked", tooltip = "foo"),
+            text("bar", visible_if = "a")
+        )]
+        d: bool,
+    }
+
+    impl Test {
+        fn visible(&self) -> bool {
+            self.a && self.b > 0.5
+        }
+
+        fn locked(&self) -> bool {
+            self.d
+        }
+
+        fn enabled(test: &Test) -> bool {
+            !test.a
+        }
+    }
+}
@@ -0,0 +1,52 @@
+#[test]
+fn context_menu() {
+    #[derive(imgui_ext::Gui, Default)]
+    #[imgui(context_menu)]
+    struct Test {
+        #[imgui(checkbox)]
+        a: bool,
+        #[imgui(slider(min = 0.0, max = 1.0, default = 0.5), input)]
+        b: f32,
+        #[imgui(drag(default = "c_default"), display)]
+        c: i32,
+        #[imgui(button(label = "Skipped"))]
+        d: u32,
+    }
+
+    fn c_default() -> i32 {
+        42
+    }
+}
+
+#[test]
+fn context_menu_items() {
+    #[derive(imgui_ext::Gui)]
+    #[imgui(context_menu(copy, paste, item(label = "Clear", call = "Self::clear")))]
+    struct Test {
+        #[imgui(input)]
+        a: f64,
+        #[imgui(drag)]
+        b: u32,
+    }
+
+    impl Test {
+        fn clear(&mut self) {
+            self.a = 0.0;
+            self.b = 0;
+        }
+    }
+}
+
+#[test]
+fn context_menu_field() {
+    #[derive(imgui_ext::Gui, Default)]
+    #[imgui(context_menu(copy))]
+    struct Test {
+        #[imgui(input(context_menu(reset, paste), default = 1.0))]
+        a: f64,
+        #[imgui(drag)]
+        b: u32,
+        #[imgui(checkbox(context_menu))]
+        c: bool,
+    }
+}
@@ -0,0 +1,30 @@
+#[test]
+fn docs_tooltip() {
+    #[derive(imgui_ext::Gui)]
+    #[imgui(docs)]
+    struct Test {
+        /// Foo.
+        #[imgui(checkbox, separator, text("bar"))]
+        a: bool,
+        /// Baz.
+        ///
+        /// Qux.
+        #[imgui(input(tooltip = "overridden"), drag(help))]
+        b: f32,
+        #[imgui(slider(min = 0.0, max = 1.0))]
+        undocumented: f32,
+    }
+}
+
+#[test]
+fn docs_verbose() {
+    #[derive(imgui_ext::Gui)]
+    #[imgui(docs = "verbose")]
+    struct Test {
+        /// Foo.
+        #[imgui(checkbox)]
+        a: bool,
+        /// Not shown, the field has no annotations.
+        b: f32,
+    }
+}
@@ -0,0 +1,77 @@
+use imgui_ext::filter::Filter;
+
+use std::collections::HashSet;
+
+#[derive(imgui_ext::Gui, Default)]
+struct Test {
+    #[imgui(slider(min = 0.0, max = 4.0))]
+    exposure: f32,
+    #[imgui(nested)]
+    shadows: Shadows,
+    #[imgui(tree(label = "Sky", node(nested)))]
+    sky: Sky,
+}
+
+#[derive(imgui_ext::Gui, Default)]
+struct Shadows {
+    #[imgui(drag(speed = 0.001))]
+    bias: f32,
+    #[imgui(checkbox(label = "Soft shadows"))]
+    soft: bool,
+}
+
+#[derive(imgui_ext::Gui, Default)]
+struct Sky {
+    #[imgui(checkbox)]
+    clouds: bool,
+    #[imgui(drag)]
+    turbidity: f32,
+}
+
+fn matches(text: &str) -> Vec<String> {
+    let mut filter = Filter::new();
+    filter.set_text(text);
+    let mut matches: Vec<_> = filter.matches::<Test>().into_iter().collect();
+    matches.sort();
+    matches
+}
+
+#[test]
+fn filter() {
+    // by label
+    assert_eq!(vec!["shadows", "shadows.soft"], matches("SOFT"));
+    // by path
+    assert_eq!(vec!["shadows", "shadows.bias"], matches("shadows.b"));
+    // every field of a matching container
+    assert_eq!(vec!["sky", "sky.clouds", "sky.turbidity"], matches("sky"));
+    assert_eq!(HashSet::new(), Filter::new().matches::<Test>());
+    assert!(Filter::new().is_empty());
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn filter_draw() {
+    use imgui_ext::testing::Harness;
+    use imgui_ext::UiExt;
+
+    let mut harness = Harness::new();
+    let mut test = Test::default();
+    let mut filter = Filter::new();
+
+    let frame = harness.frame(|ui| ui.draw_gui_filtered(&mut test, &mut filter));
+    assert!(frame.item("exposure").is_some());
+    assert!(frame.item("Soft shadows").is_some());
+
+    filter.set_text("turb");
+    let frame = harness.frame(|ui| ui.draw_gui_filtered(&mut test, &mut filter));
+    assert!(frame.item("exposure").is_none());
+    assert!(frame.item("Soft shadows").is_none());
+    assert!(frame.item("clouds").is_none());
+    // the tree node is opened
+    assert!(frame.item("turbidity").is_some());
+
+    // ...only while filtering
+    filter.set_text("");
+    let frame = harness.frame(|ui| ui.draw_gui_filtered(&mut test, &mut filter));
+    assert!(frame.item("turbidity").is_none());
+}
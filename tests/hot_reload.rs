@@ -0,0 +1,91 @@
+#![cfg(feature = "hot_reload")]
+use imgui_ext::hot_reload::HotReload;
+use serde::{Deserialize, Serialize};
+
+use std::time::Duration;
+
+#[derive(imgui_ext::Gui, Serialize, Deserialize, Debug, PartialEq)]
+struct Test {
+    #[imgui(slider(min = 0.0, max = 1.0))]
+    a: f32,
+    #[imgui(checkbox)]
+    b: bool,
+    #[imgui(nested)]
+    c: Nested,
+}
+
+#[derive(imgui_ext::Gui, Serialize, Deserialize, Debug, PartialEq)]
+struct Nested {
+    #[imgui(input)]
+    d: i32,
+    #[imgui(input)]
+    e: i32,
+}
+
+// Writes the file with a newer modification time than the previous write.
+fn write(path: &std::path::Path, text: &str) {
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    loop {
+        std::fs::write(path, text).unwrap();
+        let now = std::fs::metadata(path).unwrap().modified().unwrap();
+        if modified.map(|m| now > m).unwrap_or(true) {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn hot_reload() {
+    // unique per run, so concurrent runs don't share the file
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let path = std::env::temp_dir().join(format!(
+        "imgui_ext_hot_reload_test_{}_{}.json",
+        std::process::id(),
+        nanos
+    ));
+    write(
+        &path,
+        r#"{ "a": 0.5, "b": false, "c": { "d": 1, "e": 2 } }"#,
+    );
+
+    let mut hot = HotReload::new(&path);
+    let mut test = Test {
+        a: 0.0,
+        b: false,
+        c: Nested { d: 0, e: 0 },
+    };
+
+    // the first read replaces the value
+    assert!(hot.poll(&mut test).unwrap().is_empty());
+    assert_eq!(0.5, test.a);
+    assert_eq!(Nested { d: 1, e: 2 }, test.c);
+
+    // unmodified file
+    assert!(hot.poll(&mut test).unwrap().is_empty());
+
+    // edits made with the UI are kept, unless the field changed on disk
+    test.a = 0.25;
+    test.c.d = 8;
+    write(&path, r#"{ "a": 0.5, "b": true, "c": { "d": 1, "e": 3 } }"#);
+
+    let changed = hot.poll(&mut test).unwrap();
+    assert_eq!(vec!["b".to_string(), "c.e".to_string()], changed);
+    assert_eq!(
+        Test {
+            a: 0.25,
+            b: true,
+            c: Nested { d: 8, e: 3 },
+        },
+        test
+    );
+
+    // writes are not read back
+    hot.write(&test).unwrap();
+    assert!(hot.poll(&mut test).unwrap().is_empty());
+
+    std::fs::remove_file(&path).unwrap();
+}
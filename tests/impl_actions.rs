@@ -0,0 +1,108 @@
+use imgui::ImString;
+
+#[test]
+fn impl_actions() {
+    #[derive(imgui_ext::Gui)]
+    #[imgui(actions)]
+    struct Test {
+        #[imgui(input(catch = "count_changed"))]
+        count: i32,
+        name: ImString,
+        args: imgui_ext::ActionArgs<Self>,
+    }
+
+    #[imgui_ext::actions(args = "args")]
+    impl Test {
+        #[imgui(button)]
+        fn increment(&mut self) {
+            self.count += 1;
+        }
+
+        #[imgui(button(label = "Add", confirm = "Are you sure?"))]
+        fn add(&mut self, value: i32, twice: bool) {
+            self.count += if twice { value * 2 } else { value };
+        }
+
+        #[imgui(button(label = "Rename"))]
+        fn rename(&mut self, name: ImString) -> bool {
+            self.name = name;
+            true
+        }
+
+        #[allow(dead_code)]
+        fn not_an_action(&self) {}
+    }
+
+    fn _events(ui: &imgui::Ui, test: &mut Test) -> bool {
+        use imgui_ext::UiExt;
+        let events = ui.draw_gui(test);
+        let actions = events.actions();
+        events.count_changed() || actions.increment() || actions.add() || actions.rename()
+    }
+
+    fn _draw(ui: &imgui::Ui, test: &mut Test) -> bool {
+        use imgui_ext::UiExt;
+        ui.draw_gui_readonly(test);
+        ui.draw_actions(test).increment()
+    }
+}
+
+#[test]
+fn impl_actions_generic() {
+    struct Counter<T>
+    where
+        T: imgui_ext::input::Input<T> + std::ops::AddAssign + Default + Clone + 'static,
+    {
+        count: T,
+        args: imgui_ext::ActionArgs<Self>,
+    }
+
+    #[imgui_ext::actions(args = "args")]
+    impl<T> Counter<T>
+    where
+        T: imgui_ext::input::Input<T> + std::ops::AddAssign + Default + Clone + 'static,
+    {
+        #[imgui(button)]
+        fn add(&mut self, value: T) {
+            self.count += value;
+        }
+    }
+
+    fn _draw(ui: &imgui::Ui, counter: &mut Counter<f32>) -> bool {
+        use imgui_ext::UiExt;
+        ui.draw_actions(counter).add()
+    }
+}
+
+#[test]
+fn impl_actions_instances() {
+    #[derive(imgui_ext::Gui)]
+    #[imgui(actions)]
+    struct Counter<T> {
+        #[imgui(checkbox)]
+        enabled: bool,
+        count: T,
+    }
+
+    // every instance gets its own events type
+    #[imgui_ext::actions]
+    impl Counter<f32> {
+        #[imgui(button)]
+        fn reset(&mut self) {
+            self.count = 0.0;
+        }
+    }
+
+    #[imgui_ext::actions]
+    impl Counter<i32> {
+        #[imgui(button)]
+        fn reset(&mut self) {
+            self.count = 0;
+        }
+    }
+
+    fn _events(ui: &imgui::Ui, a: &mut Counter<f32>, b: &mut Counter<i32>) -> bool {
+        use imgui_ext::UiExt;
+        ui.draw_gui(a).actions().reset() || ui.draw_gui(b).actions().reset()
+    }
+}
@@ -0,0 +1,27 @@
+#[test]
+fn menu() {
+    #[derive(imgui_ext::Gui)]
+    #[imgui(menu_bar)]
+    struct Test {
+        #[imgui(menu)]
+        a: Menu,
+        #[imgui(menu(label = "B", catch = "b_menu"))]
+        b: Menu,
+        #[imgui(menu_item(label = "C", shortcut = "Ctrl+C"))]
+        c: bool,
+    }
+
+    #[derive(imgui_ext::Gui)]
+    struct Menu {
+        #[imgui(menu_item)]
+        d: (),
+        #[imgui(menu_item(catch = "e_toggled"))]
+        e: Option<bool>,
+    }
+
+    fn _events(ui: &imgui::Ui, test: &mut Test) -> bool {
+        use imgui_ext::UiExt;
+        let events = ui.draw_gui(test);
+        events.a().d() || events.b_menu().e_toggled() || events.c()
+    }
+}
@@ -0,0 +1,92 @@
+use imgui_ext::multi;
+
+#[derive(imgui_ext::Gui, Clone, Default)]
+struct Test {
+    #[imgui(drag)]
+    a: [f32; 3],
+    #[imgui(checkbox)]
+    b: bool,
+    #[imgui(nested)]
+    c: Nested,
+}
+
+#[derive(imgui_ext::Gui, Clone, Default)]
+struct Nested {
+    #[imgui(input)]
+    d: i32,
+}
+
+#[test]
+fn mixed() {
+    let mut values = vec![Test::default(), Test::default(), Test::default()];
+    assert!(multi::mixed(&values).is_empty());
+    assert!(multi::mixed::<Test>(&[]).is_empty());
+
+    values[1].b = true;
+    values[2].c.d = 4;
+    assert_eq!(vec!["b", "c.d"], multi::mixed(&values));
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn multi_draw() {
+    use imgui_ext::testing::Harness;
+    use imgui_ext::UiExt;
+
+    let mut values = vec![Test::default(), Test::default()];
+    values[1].b = true;
+
+    let mut harness = Harness::new();
+    let frame = harness.frame(|ui| ui.draw_gui_multi(&mut values[..]));
+    let events = frame.output().as_ref().unwrap();
+    assert_eq!(&["b"], events.mixed());
+    assert!(events.edited().is_empty());
+
+    let frame = harness.frame(|ui| ui.draw_gui_multi::<Test>(&mut []));
+    assert!(frame.output().is_none());
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn multi_driver() {
+    use imgui::Ui;
+    use imgui_ext::testing::Harness;
+    use imgui_ext::{Gui, UiExt};
+
+    // draws the selection with `draw_gui_multi`, so it can be driven
+    struct Selection(Vec<Test>);
+
+    impl Gui for Selection {
+        type Events = Option<multi::MultiEvents<<Test as Gui>::Events>>;
+        fn draw_gui(ui: &Ui, ext: &mut Self) -> Self::Events {
+            ui.draw_gui_multi(&mut ext.0[..])
+        }
+    }
+
+    let mut selection = Selection(vec![Test::default(), Test::default(), Test::default()]);
+    selection.0[1].a = [1.0, 2.0, 3.0];
+    selection.0[2].b = true;
+
+    let mut harness = Harness::new();
+    let mut driver = harness.driver(&mut selection);
+    driver.type_text("d", "7");
+    assert!(driver.fired(|events| {
+        let edited = events.as_ref().map(|events| events.edited());
+        edited == Some(&["c.d".to_string()][..])
+    }));
+
+    driver.clear_events();
+    driver.click("b");
+    assert!(driver.fired(|events| {
+        let edited = events.as_ref().map(|events| events.edited());
+        edited == Some(&["b".to_string()][..])
+    }));
+
+    // the edited fields are written into every value, the rest are untouched
+    let values = &driver.value().0;
+    assert!(values.iter().all(|value| value.c.d == 7));
+    assert!(values.iter().all(|value| value.b));
+    assert_eq!([0.0; 3], values[0].a);
+    assert_eq!([1.0, 2.0, 3.0], values[1].a);
+    assert_eq!([0.0; 3], values[2].a);
+}
@@ -0,0 +1,26 @@
+#[test]
+fn popup() {
+    #[derive(imgui_ext::Gui)]
+    struct Test {
+        #[imgui(
+            button(label = "Open", catch = "open"),
+            popup(open = "open", content(checkbox)),
+            modal(open = "a", ok = "Yes", catch = "confirm")
+        )]
+        a: bool,
+        #[imgui(modal(label = "Modal", open = "open", content(nested)))]
+        b: Nested,
+    }
+
+    #[derive(imgui_ext::Gui)]
+    struct Nested {
+        #[imgui(checkbox)]
+        c: bool,
+    }
+
+    fn _events(ui: &imgui::Ui, test: &mut Test) -> bool {
+        use imgui_ext::UiExt;
+        let events = ui.draw_gui(test);
+        events.b_ok() || events.b_cancel() || events.confirm_ok()
+    }
+}
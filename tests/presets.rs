@@ -0,0 +1,51 @@
+#![cfg(feature = "presets")]
+use imgui_ext::presets::{Error, Presets};
+use serde::{Deserialize, Serialize};
+
+#[derive(imgui_ext::Gui, Serialize, Deserialize, Debug, PartialEq)]
+struct Test {
+    #[imgui(slider(min = 0.0, max = 1.0))]
+    a: f32,
+    #[imgui(checkbox)]
+    b: bool,
+}
+
+#[test]
+fn presets() {
+    // unique per run, so concurrent runs don't share the presets
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!(
+        "imgui_ext_presets_test_{}_{}",
+        std::process::id(),
+        nanos
+    ));
+
+    let mut presets = Presets::new(&dir);
+    assert!(presets.names().is_empty());
+
+    presets.save("low", &Test { a: 0.1, b: false }).unwrap();
+    presets.save("high", &Test { a: 0.9, b: true }).unwrap();
+    assert_eq!(&["high", "low"], presets.names());
+    assert_eq!(Some("high"), presets.selected());
+
+    let low: Test = presets.load("low").unwrap();
+    assert_eq!(Test { a: 0.1, b: false }, low);
+    assert_eq!(Some("low"), presets.selected());
+
+    let revert: Test = presets.revert().unwrap().unwrap();
+    assert_eq!(low, revert);
+
+    presets.delete("low").unwrap();
+    assert_eq!(&["high"], presets.names());
+    assert!(presets.load::<Test>("low").is_err());
+
+    match presets.save("../outside", &low) {
+        Err(Error::InvalidName(_)) => {}
+        _ => panic!(),
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,52 @@
+use imgui::ImString;
+
+#[test]
+fn readonly() {
+    #[derive(imgui_ext::Gui)]
+    #[imgui(context_menu(item(label = "Unlock", call = "Self::unlock")))]
+    struct Test {
+        #[imgui(checkbox)]
+        a: bool,
+        #[imgui(input(readonly), slider(min = 0, max = 4, disabled_if = "locked"))]
+        b: i32,
+        #[imgui(drag(readonly, tooltip = "foo"), color(edit(disabled_if = "locked")))]
+        c: [f32; 4],
+        #[imgui(input(disabled_if = "locked"))]
+        d: ImString,
+        #[imgui(nested, button(label = "E", catch = "e_click"))]
+        e: Nested,
+        #[imgui(menu_item(disabled_if = "locked"))]
+        f: bool,
+    }
+
+    #[derive(imgui_ext::Gui, Clone)]
+    struct Nested {
+        #[imgui(checkbox(map = "map"))]
+        g: Option<bool>,
+    }
+
+    impl Test {
+        fn locked(&self) -> bool {
+            self.a
+        }
+
+        fn unlock(&mut self) {
+            self.a = false;
+        }
+    }
+
+    fn map(g: &mut Option<bool>) -> &mut bool {
+        g.get_or_insert(false)
+    }
+
+    fn _events(ui: &imgui::Ui, test: &mut Test) -> bool {
+        use imgui_ext::UiExt;
+        let events = ui.draw_gui(test);
+        events.a() || events.b() || events.c() || events.d() || events.e().g() || events.e_click()
+    }
+
+    fn _draw(ui: &imgui::Ui, test: &Test) {
+        use imgui_ext::UiExt;
+        ui.draw_gui_readonly(test);
+    }
+}
@@ -0,0 +1,88 @@
+use imgui_ext::registry::{self, Tweak};
+use imgui_ext::tweak;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+static EXPOSURE: Mutex<f32> = Mutex::new(1.0);
+
+#[test]
+fn registry() {
+    assert_eq!(
+        0.005,
+        tweak!("render/shadows/bias", 0.005_f32, drag(0.0001))
+    );
+    assert!(tweak!("render/shadows/enabled", true, checkbox()));
+
+    let samples = Arc::new(Mutex::new(4_i32));
+    let color = Rc::new(RefCell::new([1.0_f32, 1.0, 1.0]));
+    registry::register("render/exposure", &EXPOSURE, Tweak::slider(0.0_f32, 4.0));
+    registry::register("render/shadows/samples", samples, Tweak::input());
+    registry::register("sky/color", color.clone(), Tweak::color());
+
+    assert_eq!(
+        vec![
+            "render/exposure",
+            "render/shadows/bias",
+            "render/shadows/enabled",
+            "render/shadows/samples",
+            "sky/color",
+        ],
+        registry::paths()
+    );
+
+    // the value is only initialized once
+    assert_eq!(0.005, tweak!("render/shadows/bias", 1.0_f32, drag(0.0001)));
+
+    registry::unregister("sky/color");
+    assert_eq!(4, registry::paths().len());
+}
+
+#[test]
+#[should_panic]
+fn registry_type() {
+    tweak!("a", 0.5_f32, drag(0.1));
+    tweak!("a", true, checkbox());
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn registry_draw() {
+    use imgui_ext::testing::Harness;
+    use imgui_ext::UiExt;
+
+    tweak!("render/shadows/bias", 0.005_f32, drag(0.0001));
+    tweak!("render/exposure", 1.0_f32, slider(0.0_f32, 4.0));
+
+    let mut harness = Harness::new();
+    let frame = harness.frame(|ui| ui.draw_registry());
+    assert!(frame.output().is_empty());
+    assert!(frame.vertex_count() > 0);
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn registry_draw_register() {
+    use imgui_ext::registry::Source;
+    use imgui_ext::testing::Harness;
+    use imgui_ext::UiExt;
+
+    // registers another value while it is drawn
+    struct Registering;
+
+    impl Source<bool> for Registering {
+        fn with<R>(&self, f: impl FnOnce(&mut bool) -> R) -> Option<R> {
+            tweak!("draw/other", 1, input());
+            Some(f(&mut false))
+        }
+    }
+
+    registry::register("draw/registering", Registering, Tweak::checkbox());
+    // a leaf and a group with the same label
+    tweak!("draw/other/nested", 0.5_f32, drag(0.1));
+
+    let mut harness = Harness::new();
+    harness.frame(|ui| ui.draw_registry());
+    assert!(registry::paths().contains(&"draw/other".to_string()));
+}
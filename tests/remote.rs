@@ -0,0 +1,121 @@
+#![cfg(feature = "remote")]
+use imgui_ext::remote::{Client, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+#[derive(imgui_ext::Gui, Serialize, Deserialize, Debug, PartialEq)]
+struct Test {
+    #[imgui(slider(min = 0.0, max = 1.0), button(label = "Reset"))]
+    a: f32,
+    #[imgui(nested)]
+    b: Nested,
+    c: i32,
+}
+
+#[derive(imgui_ext::Gui, Serialize, Deserialize, Debug, PartialEq)]
+struct Nested {
+    #[imgui(checkbox)]
+    d: bool,
+    e: i32,
+}
+
+#[test]
+fn remote() {
+    let value = Arc::new(Mutex::new(Test {
+        a: 0.5,
+        b: Nested { d: false, e: 1 },
+        c: 4,
+    }));
+
+    let server = Server::bind("127.0.0.1:0").unwrap();
+    server.register("test", value.clone());
+
+    let mut client = Client::connect(server.local_addr()).unwrap();
+
+    let list = client.list().unwrap().unwrap();
+    let schema = &list["test"];
+    assert_eq!("slider", schema.widgets[0].kind);
+    assert_eq!("button", schema.widgets[1].kind);
+    assert_eq!("nested", schema.widgets[2].kind);
+
+    let get = client.get("test", None).unwrap().unwrap();
+    assert_eq!(json!({ "a": 0.5, "b": { "d": false, "e": 1 }, "c": 4 }), get);
+    let get = client.get("test", Some("b.d")).unwrap().unwrap();
+    assert_eq!(json!(false), get);
+
+    client.set("test", "a", json!(0.25)).unwrap().unwrap();
+    client.set("test", "b.d", json!(true)).unwrap().unwrap();
+    assert_eq!(0.25, value.lock().unwrap().a);
+    assert!(value.lock().unwrap().b.d);
+
+    // not annotated, or the wrong type
+    assert!(client.set("test", "c", json!(2)).unwrap().is_err());
+    assert!(client.set("test", "b.e", json!(2)).unwrap().is_err());
+    assert!(client.set("test", "a", json!("x")).unwrap().is_err());
+    assert!(client.get("missing", None).unwrap().is_err());
+
+    client.press("test", "Reset").unwrap().unwrap();
+    client.press("test", "Reset").unwrap().unwrap();
+    assert!(client.press("test", "Missing").unwrap().is_err());
+    assert_eq!(vec!["Reset", "Reset"], server.pressed("test"));
+    assert!(server.pressed("test").is_empty());
+
+    // raw protocol
+    let response = client.request(&json!({ "cmd": "unknown" })).unwrap();
+    assert!(response.is_err());
+
+    server.unregister("test");
+    assert!(client.list().unwrap().unwrap().is_empty());
+
+    // the connections are shut down with the server
+    drop(server);
+    assert!(client.list().map(|list| list.is_err()).unwrap_or(true));
+}
+
+fn test() -> Arc<Mutex<Test>> {
+    Arc::new(Mutex::new(Test {
+        a: 0.5,
+        b: Nested { d: false, e: 1 },
+        c: 4,
+    }))
+}
+
+#[test]
+fn remote_locked() {
+    let value = test();
+    let server = Server::bind("127.0.0.1:0").unwrap();
+    server.register("test", value.clone());
+    let addr = server.local_addr();
+
+    // a request waiting on a value locked by the application doesn't block
+    // the registry
+    let lock = value.lock().unwrap();
+    let request = thread::spawn(move || Client::connect(addr).unwrap().get("test", None));
+    thread::sleep(Duration::from_millis(100));
+    server.register("other", test());
+    server.unregister("other");
+    drop(lock);
+    assert!(request.join().unwrap().unwrap().is_ok());
+}
+
+#[test]
+fn remote_too_long() {
+    let server = Server::bind("127.0.0.1:0").unwrap();
+    server.register("test", test());
+
+    // the request is rejected, and the connection closed
+    let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+    stream.write_all(&vec![b' '; 1 << 20]).unwrap();
+    let mut stream = BufReader::new(stream);
+    let mut line = String::new();
+    stream.read_line(&mut line).unwrap();
+    assert!(line.contains("error"));
+    line.clear();
+    assert_eq!(0, stream.read_line(&mut line).unwrap());
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "schema")]
+use imgui_ext::schema::{Param, Schema};
+
+#[derive(imgui_ext::Gui)]
+struct Test {
+    #[imgui(drag(min = "-4", max = 4, speed = 0.5), display)]
+    a: [i32; 2],
+    #[imgui(tree(label = "Tree", node(nested)))]
+    b: Nested,
+    #[imgui(nested(map = "unwrap"))]
+    c: Option<Nested>,
+}
+
+#[derive(imgui_ext::Gui)]
+struct Nested {
+    #[imgui(input(label = "Input##d", step = 1.0))]
+    d: f32,
+}
+
+fn unwrap(c: &mut Option<Nested>) -> &mut Nested {
+    c.as_mut().unwrap()
+}
+
+#[test]
+fn schema() {
+    let schema = Schema::of::<Test>();
+
+    let kinds: Vec<_> = schema.widgets.iter().map(|w| w.kind.as_str()).collect();
+    assert_eq!(vec!["drag", "display", "tree", "nested"], kinds);
+
+    let drag = &schema.widgets[0];
+    assert_eq!("[i32;2]", drag.ty);
+    assert_eq!(Some(&Param::Int(-4)), drag.params.get("min"));
+    assert_eq!(Some(&Param::Int(4)), drag.params.get("max"));
+    assert_eq!(Some(&Param::Float(0.5)), drag.params.get("speed"));
+
+    let tree = &schema.widgets[2];
+    assert_eq!("Tree", tree.label);
+    assert_eq!("nested", tree.children[0].kind);
+    assert_eq!("Input##d", tree.children[0].children[0].label);
+
+    let nested = &schema.widgets[3];
+    assert_eq!("c", nested.field);
+    assert_eq!("f32", nested.children[0].ty);
+}
+
+#[test]
+fn json() {
+    let schema = Schema::of::<Test>();
+    let json = serde_json::to_string(&schema).unwrap();
+
+    assert!(json.contains(r#""type":"[i32;2]""#));
+    assert_eq!(schema, serde_json::from_str(&json).unwrap());
+}
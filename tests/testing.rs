@@ -0,0 +1,122 @@
+#![cfg(feature = "testing")]
+use imgui_ext::testing::{self, Harness};
+
+#[derive(imgui_ext::Gui)]
+struct Test {
+    #[imgui(checkbox(label = "Enabled"))]
+    a: bool,
+    #[imgui(slider(min = 0.0, max = 1.0, catch = "b_changed"))]
+    b: f32,
+    #[imgui(input(label = "Input##c"), button(label = "Reset##c"))]
+    c: i32,
+}
+
+#[test]
+fn items() {
+    let mut harness = Harness::new();
+    let mut test = Test {
+        a: true,
+        b: 0.5,
+        c: 0,
+    };
+
+    let frame = harness.draw_gui(&mut test);
+
+    let kinds: Vec<_> = frame.items().iter().map(|item| item.kind).collect();
+    assert_eq!(vec!["checkbox", "slider", "input", "button"], kinds);
+
+    assert_eq!("checkbox", frame.item("Enabled").unwrap().kind);
+    assert_eq!("Input", frame.item("Input##c").unwrap().name());
+    assert!(frame.item("Reset").is_some());
+    assert!(frame.item("a").is_none());
+    assert_eq!(1, frame.items_of("slider").len());
+
+    let b = frame.item("b").unwrap();
+    assert!(b.max[0] > b.min[0] && b.max[1] > b.min[1]);
+
+    assert!(!frame.events().b_changed());
+    assert!(!frame.events().c());
+}
+
+#[test]
+fn draw_lists() {
+    let mut harness = Harness::new();
+    let mut test = Test {
+        a: false,
+        b: 0.0,
+        c: 0,
+    };
+
+    let empty = harness.frame(|_| ());
+    let frame = harness.draw_gui(&mut test);
+    let readonly = harness.draw_gui_readonly(&test);
+
+    assert!(frame.vertex_count() > empty.vertex_count());
+    assert!(frame.index_count() > empty.index_count());
+    assert!(frame.command_count() > 0);
+    assert_eq!(frame.items().len(), readonly.items().len());
+}
+
+#[test]
+fn driver() {
+    let mut harness = Harness::new();
+    let mut test = Test {
+        a: false,
+        b: 0.0,
+        c: 0,
+    };
+
+    let mut driver = harness.driver(&mut test);
+
+    driver.click("Enabled");
+    assert!(driver.value().a);
+    assert!(driver.fired(|events| events.a()));
+
+    driver.clear_events();
+    driver.type_text("Input##c", "42");
+    assert_eq!(42, driver.value().c);
+    assert!(driver.fired(|events| events.c()));
+    assert!(!driver.fired(|events| events.a()));
+
+    driver.clear_events();
+    driver.drag("b", [100.0, 0.0]);
+    assert!(driver.value().b > 0.0);
+    assert!(driver.fired(|events| events.b_changed()));
+}
+
+#[test]
+fn snapshot() {
+    #[derive(imgui_ext::Gui)]
+    struct Snap {
+        #[imgui(checkbox(label = "Enabled"))]
+        a: bool,
+        #[imgui(nested)]
+        b: Inner,
+        #[imgui(button(label = "Reset"))]
+        c: (),
+    }
+
+    #[derive(imgui_ext::Gui)]
+    struct Inner {
+        #[imgui(slider(min = 0.0, max = 1.0))]
+        x: f32,
+    }
+
+    let mut harness = Harness::new();
+    let mut snap = Snap {
+        a: true,
+        b: Inner { x: 0.5 },
+        c: (),
+    };
+
+    let snapshot = harness.draw_gui(&mut snap).snapshot();
+
+    assert_eq!(3, snapshot.nodes.len());
+    assert_eq!("slider", snapshot.nodes[1].children[0].kind);
+    assert_eq!(Some("0.5"), snapshot.nodes[1].children[0].value.as_deref());
+
+    testing::assert_snapshot(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots/testing.snap"),
+        &snapshot,
+    );
+}
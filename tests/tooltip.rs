@@ -0,0 +1,34 @@
+#[test]
+fn tooltip() {
+    #[derive(imgui_ext::Gui)]
+    struct Test {
+        #[imgui(checkbox(tooltip = "foo"))]
+        a: bool,
+        /// Documented field.
+        #[imgui(input(tooltip, help), drag(help = "bar"))]
+        b: f32,
+        /// Documented field.
+        #[imgui(color(edit(tooltip), picker(help)))]
+        c: [f32; 4],
+        #[imgui(text("baz", tooltip = "qux"), tree(node(nested), help = "quux"))]
+        d: Nested,
+        #[imgui(
+            vars(content(input), tooltip = "grault"),
+            child(content(drag), help = "garply")
+        )]
+        e: f32,
+        // `help` is a field of the formatted text
+        #[imgui(display(display = "{}", help))]
+        help: Help,
+    }
+
+    struct Help {
+        help: u32,
+    }
+
+    #[derive(imgui_ext::Gui)]
+    struct Nested {
+        #[imgui(slider(min = 0.0, max = 1.0, help = "corge"))]
+        e: f32,
+    }
+}
@@ -0,0 +1,7 @@
+#[derive(imgui_ext::Gui)]
+struct Test {
+    #[imgui(checkbox(label = "A", label = "B"))]
+    value: bool,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(imgui_ext::Gui)]
+struct Test {
+    #[imgui(bullet(input, checkbox))]
+    value: f32,
+}
+
+fn main() {}
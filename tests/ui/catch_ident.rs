@@ -0,0 +1,7 @@
+#[derive(imgui_ext::Gui)]
+struct Test {
+    #[imgui(checkbox(catch = "my event"))]
+    value: bool,
+}
+
+fn main() {}
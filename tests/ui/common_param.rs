@@ -0,0 +1,7 @@
+#[derive(imgui_ext::Gui)]
+struct Test {
+    #[imgui(input(on_chnage = "changed"))]
+    value: f32,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[derive(imgui_ext::Gui)]
+#[imgui(doc)]
+struct Test {
+    #[imgui(checkbox)]
+    value: bool,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(imgui_ext::Gui)]
+struct Test {
+    #[imgui(slider(min = 0.0, max = 1.0, default = 0.5))]
+    a: f32,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(imgui_ext::Gui)]
+struct Test {
+    #[imgui(checkbox(disabled_if = "Self::locked"))]
+    value: bool,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(imgui_ext::Gui)]
+struct Test {
+    #[imgui(input(step))]
+    value: f32,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(imgui_ext::Gui)]
+struct Test {
+    #[imgui(checkbox(label = 42))]
+    value: bool,
+}
+
+fn main() {}
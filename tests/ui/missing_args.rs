@@ -0,0 +1,13 @@
+struct Test {
+    value: u32,
+}
+
+#[imgui_ext::actions]
+impl Test {
+    #[imgui(button)]
+    fn set(&mut self, value: u32) {
+        self.value = value;
+    }
+}
+
+fn main() {}
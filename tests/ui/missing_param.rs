@@ -0,0 +1,9 @@
+#[derive(imgui_ext::Gui)]
+struct Test {
+    #[imgui(slider(min = 0.0))]
+    value: f32,
+    #[imgui(button)]
+    click: (),
+}
+
+fn main() {}
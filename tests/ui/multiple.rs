@@ -0,0 +1,8 @@
+#[derive(imgui_ext::Gui)]
+struct Test {
+    #[imgui(checkbox)]
+    #[imgui(checkbox)]
+    value: bool,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[derive(imgui_ext::Gui)]
+enum Enum {
+    A,
+}
+
+#[derive(imgui_ext::Gui)]
+struct Tuple(#[imgui(checkbox)] bool);
+
+fn main() {}
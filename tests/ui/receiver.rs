@@ -0,0 +1,11 @@
+struct Test {
+    value: u32,
+}
+
+#[imgui_ext::actions]
+impl Test {
+    #[imgui]
+    fn reset(&self) {}
+}
+
+fn main() {}
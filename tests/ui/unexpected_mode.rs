@@ -0,0 +1,7 @@
+#[derive(imgui_ext::Gui)]
+struct Test {
+    #[imgui(color)]
+    value: [f32; 4],
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(imgui_ext::Gui)]
+struct Test {
+    #[imgui(sldier(min = 0.0, max = 1.0))]
+    value: f32,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(imgui_ext::Gui)]
+struct Test {
+    #[imgui(input(step_fst = 10.0))]
+    value: f32,
+}
+
+fn main() {}
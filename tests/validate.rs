@@ -0,0 +1,90 @@
+use imgui::ImString;
+
+#[test]
+fn validate() {
+    #[derive(imgui_ext::Gui)]
+    struct Test {
+        #[imgui(input(clamp(0, 10)), drag(clamp("-1", "1"), on_change = "changed"))]
+        a: i32,
+        #[imgui(drag(min = 0.0, max = 1.0, clamp(0.0, 1.0)))]
+        b: [f32; 3],
+        #[imgui(input(validate = "not_empty", on_change = "changed"))]
+        c: ImString,
+        #[imgui(checkbox(on_change = "changed", catch = "d_changed"))]
+        d: bool,
+        #[imgui(slider(min = 0.0, max = 1.0, clamp(0.25, 0.75), visible_if = "d"))]
+        e: Option<f32>,
+        #[imgui(input(clamp(0, 100)))]
+        f: f64,
+        #[imgui(drag(map = "first", clamp(0, 1)))]
+        g: ([f32; 2], u8),
+        changes: usize,
+    }
+
+    impl Test {
+        fn changed(&mut self) {
+            self.changes += 1;
+        }
+    }
+
+    fn first(g: &mut ([f32; 2], u8)) -> &mut [f32; 2] {
+        &mut g.0
+    }
+
+    fn not_empty(text: &ImString) -> Result<(), String> {
+        if text.to_str().is_empty() {
+            Err("Empty".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn _events(ui: &imgui::Ui, test: &mut Test) -> bool {
+        use imgui_ext::UiExt;
+        let events = ui.draw_gui(test);
+        events.a() || events.b() || events.c() || events.d_changed() || events.e()
+    }
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn validate_driver() {
+    use imgui_ext::testing::Harness;
+
+    #[derive(imgui_ext::Gui)]
+    struct Test {
+        #[imgui(input(label = "Even", validate = "even", on_change = "changed"))]
+        even: i32,
+        changes: usize,
+    }
+
+    impl Test {
+        fn changed(&mut self) {
+            self.changes += 1;
+        }
+    }
+
+    fn even(value: &i32) -> Result<(), String> {
+        if value % 2 == 0 {
+            Ok(())
+        } else {
+            Err("Odd".to_string())
+        }
+    }
+
+    let mut harness = Harness::new();
+    let mut test = Test { even: 0, changes: 0 };
+
+    // invalid edits are kept, but not reported
+    let mut driver = harness.driver(&mut test);
+    driver.type_text("Even", "3");
+    assert_eq!(3, driver.value().even);
+    assert_eq!(0, driver.value().changes);
+    assert!(!driver.fired(|events| events.even()));
+
+    driver.clear_events();
+    driver.type_text("Even", "4");
+    assert_eq!(4, driver.value().even);
+    assert!(driver.value().changes > 0);
+    assert!(driver.fired(|events| events.even()));
+}
@@ -0,0 +1,158 @@
+use imgui_ext::visit::{Param, ValueMut, Visitor, Widget};
+use imgui_ext::Visit;
+
+#[derive(imgui_ext::Gui)]
+struct Test {
+    #[imgui(slider(min = "-1.0", max = 1.0), input(step = 2.0))]
+    a: f32,
+    #[imgui(checkbox(label = "B"))]
+    b: bool,
+    #[imgui(nested)]
+    c: Nested,
+    #[imgui(separator, button(label = "D"))]
+    d: (),
+}
+
+#[derive(imgui_ext::Gui)]
+struct Nested {
+    #[imgui(drag(speed = 0.5))]
+    e: [i32; 2],
+    #[imgui(display)]
+    f: String,
+}
+
+#[derive(Default)]
+struct Log(Vec<String>);
+
+impl Visitor for Log {
+    fn widget(&mut self, widget: &Widget, value: ValueMut) {
+        let value = match value {
+            ValueMut::F32(v) => {
+                *v += 1.0;
+                "f32"
+            }
+            ValueMut::Bool(v) => {
+                *v = !*v;
+                "bool"
+            }
+            ValueMut::I32Array(v) => {
+                v[1] = 4;
+                "[i32]"
+            }
+            ValueMut::Other => "other",
+            ValueMut::None => "none",
+            _ => unreachable!(),
+        };
+        self.0
+            .push(format!("{} {} {}", widget.kind, widget.label, value));
+    }
+
+    fn begin(&mut self, widget: &Widget) {
+        self.0
+            .push(format!("begin {} {}", widget.kind, widget.label));
+    }
+
+    fn end(&mut self, widget: &Widget) {
+        self.0.push(format!("end {} {}", widget.kind, widget.label));
+    }
+}
+
+struct Params(Vec<(String, Param<'static>)>);
+
+impl Visitor for Params {
+    fn widget(&mut self, widget: &Widget, _: ValueMut) {
+        for (name, param) in widget.params {
+            let param = match param {
+                Param::Str(_) => continue,
+                Param::Bool(b) => Param::Bool(*b),
+                Param::Int(i) => Param::Int(*i),
+                Param::Float(f) => Param::Float(*f),
+            };
+            self.0.push((format!("{}.{}", widget.field, name), param));
+        }
+    }
+}
+
+#[test]
+fn visit() {
+    let mut test = Test {
+        a: 0.0,
+        b: false,
+        c: Nested {
+            e: [0, 0],
+            f: String::new(),
+        },
+        d: (),
+    };
+
+    let mut log = Log::default();
+    Visit::visit(&mut test, &mut log);
+
+    assert_eq!(
+        vec![
+            "slider a f32",
+            "input a f32",
+            "checkbox B bool",
+            "begin nested c",
+            "drag e [i32]",
+            "display f other",
+            "end nested c",
+            "separator  none",
+            "button D none",
+        ],
+        log.0
+    );
+
+    assert_eq!(2.0, test.a);
+    assert!(test.b);
+    assert_eq!([0, 4], test.c.e);
+}
+
+#[test]
+fn params() {
+    let mut test = Test {
+        a: 0.0,
+        b: false,
+        c: Nested {
+            e: [0, 0],
+            f: String::new(),
+        },
+        d: (),
+    };
+
+    let mut params = Params(Vec::new());
+    Visit::visit(&mut test, &mut params);
+
+    assert_eq!(
+        vec![
+            ("a.min".to_string(), Param::Float(-1.0)),
+            ("a.max".to_string(), Param::Float(1.0)),
+            ("a.step".to_string(), Param::Float(2.0)),
+            ("e.speed".to_string(), Param::Float(0.5)),
+        ],
+        params.0
+    );
+}
+
+struct Manual;
+
+impl imgui_ext::Gui for Manual {
+    type Events = ();
+    fn draw_gui(_: &imgui::Ui, _: &mut Self) {}
+}
+
+// nesting a type that doesn't implement `Visit` still derives `Gui`
+#[derive(imgui_ext::Gui)]
+#[allow(dead_code)]
+struct WithManual<T> {
+    #[imgui(nested)]
+    manual: Manual,
+    #[imgui(nested)]
+    nested: Nested,
+    other: T,
+}
+
+fn _with_manual(ui: &imgui::Ui, ext: &mut WithManual<u8>) {
+    use imgui_ext::UiExt;
+    ui.draw_gui(ext);
+}